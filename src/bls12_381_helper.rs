@@ -0,0 +1,141 @@
+use crate::curve::{Curve, Point, Scalar};
+use bls12_381::hash_to_curve::{ExpandMsgXmd, HashToCurve};
+use bls12_381::{G1Affine, G1Projective, G2Affine, G2Projective, Scalar as Bls12381Scalar};
+use rand::Rng;
+use sha2::Sha256;
+use std::convert::TryInto;
+
+/// The G1 group of BLS12-381 with `Fr` scalars, backed by the `bls12_381` crate.
+///
+/// Points are encoded in the 48 bytes compressed form.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub struct Bls12381G1;
+
+impl Curve for Bls12381G1 {
+    type Scalar = Bls12381Scalar;
+    type Point = G1Projective;
+}
+
+/// The G2 group of BLS12-381 with `Fr` scalars, backed by the `bls12_381` crate.
+///
+/// Points are encoded in the 96 bytes compressed form.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub struct Bls12381G2;
+
+impl Curve for Bls12381G2 {
+    type Scalar = Bls12381Scalar;
+    type Point = G2Projective;
+}
+
+impl Scalar for Bls12381Scalar {
+    fn zero() -> Self {
+        Bls12381Scalar::zero()
+    }
+
+    fn one() -> Self {
+        Bls12381Scalar::one()
+    }
+
+    fn from_u64(n: u64) -> Self {
+        Bls12381Scalar::from(n)
+    }
+
+    fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        let mut bytes = [0u8; 64];
+        rng.fill(&mut bytes[..]);
+        Bls12381Scalar::from_bytes_wide(&bytes)
+    }
+
+    fn inverse(&self) -> Self {
+        self.invert().unwrap_or(Bls12381Scalar::zero())
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        Bls12381Scalar::to_bytes(self).to_vec()
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let bytes: [u8; 32] = bytes.try_into().ok()?;
+        Bls12381Scalar::from_bytes(&bytes).into()
+    }
+}
+
+impl Point for G1Projective {
+    type Scalar = Bls12381Scalar;
+    type Curve = Bls12381G1;
+
+    fn generator() -> Self {
+        G1Projective::generator()
+    }
+
+    fn identity() -> Self {
+        G1Projective::identity()
+    }
+
+    /// The `BLS12381G1_XMD:SHA-256_SSWU_RO_` suite of RFC 9380.
+    fn hash_to_curve(data: &[u8]) -> Self {
+        <G1Projective as HashToCurve<ExpandMsgXmd<Sha256>>>::hash_to_curve(
+            data,
+            b"VERIFIABLE_SECRET_SHARING-V01-CS01-with-BLS12381G1_XMD:SHA-256_SSWU_RO_",
+        )
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        G1Affine::from(self).to_compressed().to_vec()
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let bytes: [u8; 48] = bytes.try_into().ok()?;
+        let point: Option<G1Affine> = G1Affine::from_compressed(&bytes).into();
+        point.map(G1Projective::from)
+    }
+}
+
+impl Point for G2Projective {
+    type Scalar = Bls12381Scalar;
+    type Curve = Bls12381G2;
+
+    fn generator() -> Self {
+        G2Projective::generator()
+    }
+
+    fn identity() -> Self {
+        G2Projective::identity()
+    }
+
+    /// The `BLS12381G2_XMD:SHA-256_SSWU_RO_` suite of RFC 9380.
+    fn hash_to_curve(data: &[u8]) -> Self {
+        <G2Projective as HashToCurve<ExpandMsgXmd<Sha256>>>::hash_to_curve(
+            data,
+            b"VERIFIABLE_SECRET_SHARING-V01-CS01-with-BLS12381G2_XMD:SHA-256_SSWU_RO_",
+        )
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        G2Affine::from(self).to_compressed().to_vec()
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let bytes: [u8; 96] = bytes.try_into().ok()?;
+        let point: Option<G2Affine> = G2Affine::from_compressed(&bytes).into();
+        point.map(G2Projective::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curve::tests::{encoding_round_trips, split_verify_recover};
+
+    #[test]
+    fn test_bls12_381_g1_backend() {
+        split_verify_recover(Bls12381G1);
+        encoding_round_trips::<Bls12381G1>();
+    }
+
+    #[test]
+    fn test_bls12_381_g2_backend() {
+        split_verify_recover(Bls12381G2);
+        encoding_round_trips::<Bls12381G2>();
+    }
+}
@@ -0,0 +1,230 @@
+use crate::secp256k1_helper::{Secp256k1Point, Secp256k1Scalar};
+use rand::Rng;
+use std::fmt::Debug;
+use std::ops::{Add, Mul, Neg, Sub};
+
+/// An element of the scalar field of a prime order group.
+pub trait Scalar:
+    'static
+    + Copy
+    + Debug
+    + Eq
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Neg<Output = Self>
+{
+    /// Return the additive identity `0`.
+    fn zero() -> Self;
+
+    /// Return the multiplicative identity `1`.
+    fn one() -> Self;
+
+    /// Map a small integer, such as a share index, into the field.
+    fn from_u64(n: u64) -> Self;
+
+    /// Sample a uniformly random non-zero scalar from the given generator.
+    fn random<R: Rng + ?Sized>(rng: &mut R) -> Self;
+
+    /// Calculate the multiplicative inverse. Every backend maps zero, which
+    /// has none, to zero instead of panicking.
+    fn inverse(&self) -> Self;
+
+    /// Serialize the scalar in the canonical encoding of the curve.
+    fn to_bytes(&self) -> Vec<u8>;
+
+    /// Parse the canonical encoding, rejecting non-reduced values.
+    fn from_bytes(bytes: &[u8]) -> Option<Self>;
+}
+
+/// An element of a prime order group, written additively.
+pub trait Point:
+    'static
+    + Copy
+    + Debug
+    + Eq
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Neg<Output = Self>
+    + Mul<<Self as Point>::Scalar, Output = Self>
+{
+    /// The scalar field of the group.
+    type Scalar: Scalar;
+
+    /// The curve backend of the group, so generic code can name it from a point.
+    type Curve: Curve<Scalar = Self::Scalar, Point = Self>;
+
+    /// Return the standard generator of the group.
+    fn generator() -> Self;
+
+    /// Return the identity element.
+    fn identity() -> Self;
+
+    /// Map arbitrary data to a point with unknown discrete logarithm.
+    fn hash_to_curve(data: &[u8]) -> Self;
+
+    /// Serialize the point in the canonical compressed encoding of the curve.
+    fn to_bytes(&self) -> Vec<u8>;
+
+    /// Parse the canonical compressed encoding, rejecting points not in the group.
+    fn from_bytes(bytes: &[u8]) -> Option<Self>;
+
+    /// Calculate `generator() * scalar`, with a fixed-base table where the backend has one.
+    fn mul_generator(scalar: &Self::Scalar) -> Self {
+        Self::generator() * *scalar
+    }
+
+    /// Calculate the multi-scalar multiplication `sum points[i] * scalars[i]`.
+    fn multiscalar_mul(scalars: &[Self::Scalar], points: &[Self]) -> Self {
+        scalars
+            .iter()
+            .zip(points.iter())
+            .fold(Self::identity(), |sum, (scalar, point)| {
+                sum + *point * *scalar
+            })
+    }
+}
+
+/// A curve backend for `VerifiableSecretSharing`, tying a point type to its scalars.
+pub trait Curve: Copy + Debug + Default {
+    /// the scalar field of the curve.
+    type Scalar: Scalar;
+    /// the points of the prime order group.
+    type Point: Point<Scalar = Self::Scalar, Curve = Self>;
+}
+
+/// The secp256k1 curve, backed by `Secp256k1Scalar` and `Secp256k1Point`.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub struct Secp256k1;
+
+impl Curve for Secp256k1 {
+    type Scalar = Secp256k1Scalar;
+    type Point = Secp256k1Point;
+}
+
+impl Scalar for Secp256k1Scalar {
+    fn zero() -> Self {
+        Secp256k1Scalar::zero()
+    }
+
+    fn one() -> Self {
+        Secp256k1Scalar::one()
+    }
+
+    fn from_u64(n: u64) -> Self {
+        Secp256k1Scalar::from_u64(n)
+    }
+
+    fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        Secp256k1Scalar::random(rng)
+    }
+
+    fn inverse(&self) -> Self {
+        self.inv()
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        self.serialize().to_vec()
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() == 32 && bytes.iter().all(|b| *b == 0) {
+            return Some(Secp256k1Scalar::zero());
+        }
+        Secp256k1Scalar::from_slice(bytes)
+    }
+}
+
+impl Point for Secp256k1Point {
+    type Scalar = Secp256k1Scalar;
+    type Curve = Secp256k1;
+
+    fn generator() -> Self {
+        Secp256k1Point::generator()
+    }
+
+    fn mul_generator(scalar: &Secp256k1Scalar) -> Self {
+        Secp256k1Point::mul_generator(scalar)
+    }
+
+    fn identity() -> Self {
+        Secp256k1Point::identity()
+    }
+
+    fn hash_to_curve(data: &[u8]) -> Self {
+        Secp256k1Point::hash_to_curve(data)
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        self.serialize().to_vec()
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        Secp256k1Point::from_slice(bytes)
+    }
+
+    fn multiscalar_mul(scalars: &[Secp256k1Scalar], points: &[Self]) -> Self {
+        Secp256k1Point::multiscalar_mul(scalars, points)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::feldman_vss::VerifiableSecretSharing;
+    use rand::thread_rng;
+
+    type Dealing<C> = (
+        <C as Curve>::Scalar,
+        Vec<(usize, <C as Curve>::Scalar)>,
+        Vec<<C as Curve>::Point>,
+    );
+
+    /// Deal a random secret with Feldman's scheme, for the tests of the
+    /// threshold protocols built on a sharing.
+    pub(crate) fn deal<C: Curve>(curve: C, threshold: usize, share_amount: usize) -> Dealing<C> {
+        let secret = C::Scalar::random(&mut thread_rng());
+        let vss = VerifiableSecretSharing {
+            threshold,
+            share_amount,
+            curve,
+        };
+        let (shares, commitments) = vss.split(&secret);
+        (secret, shares, commitments)
+    }
+
+    pub(crate) fn split_verify_recover<C: Curve>(curve: C) {
+        let secret = C::Scalar::random(&mut thread_rng());
+        let vss = VerifiableSecretSharing {
+            threshold: 3,
+            share_amount: 5,
+            curve,
+        };
+        let (shares, commitments) = vss.split(&secret);
+        assert_eq!(commitments[0], C::Point::generator() * secret);
+        for share in shares.iter() {
+            assert!(VerifiableSecretSharing::verify(*share, &commitments));
+        }
+        assert_eq!(vss.recover(&shares[2..]), secret);
+    }
+
+    pub(crate) fn encoding_round_trips<C: Curve>() {
+        let scalar = C::Scalar::random(&mut thread_rng());
+        assert_eq!(C::Scalar::from_bytes(&scalar.to_bytes()), Some(scalar));
+        let zero = C::Scalar::zero();
+        assert_eq!(C::Scalar::from_bytes(&zero.to_bytes()), Some(zero));
+        let point = C::Point::generator() * scalar;
+        assert_eq!(C::Point::from_bytes(&point.to_bytes()), Some(point));
+        let identity = C::Point::identity();
+        assert_eq!(C::Point::from_bytes(&identity.to_bytes()), Some(identity));
+        assert_eq!(point + -point, identity);
+        assert_eq!(scalar * scalar.inverse(), C::Scalar::one());
+        assert_eq!(zero.inverse(), zero);
+    }
+
+    #[test]
+    fn test_secp256k1_backend() {
+        split_verify_recover(Secp256k1);
+        encoding_round_trips::<Secp256k1>();
+    }
+}
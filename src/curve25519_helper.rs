@@ -0,0 +1,228 @@
+use crate::curve::{Curve, Point, Scalar};
+use curve25519_dalek::constants::{ED25519_BASEPOINT_POINT, RISTRETTO_BASEPOINT_POINT};
+use curve25519_dalek::edwards::{CompressedEdwardsY, EdwardsPoint};
+use curve25519_dalek::ristretto::{CompressedRistretto, RistrettoPoint};
+use curve25519_dalek::scalar::Scalar as Curve25519Scalar;
+use curve25519_dalek::traits::{Identity, IsIdentity};
+use rand::Rng;
+use sha2::{Digest, Sha512};
+use std::convert::TryInto;
+
+/// The Ristretto255 prime order group, backed by `curve25519-dalek`.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub struct Ristretto255;
+
+impl Curve for Ristretto255 {
+    type Scalar = Curve25519Scalar;
+    type Point = RistrettoPoint;
+}
+
+/// The prime order subgroup of the Ed25519 curve, backed by `curve25519-dalek`.
+///
+/// Points are checked to be torsion free when parsed, so shares and
+/// commitments never leave the subgroup generated by the Ed25519 base point.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub struct Ed25519;
+
+impl Curve for Ed25519 {
+    type Scalar = Curve25519Scalar;
+    type Point = EdwardsPoint;
+}
+
+impl Scalar for Curve25519Scalar {
+    fn zero() -> Self {
+        Curve25519Scalar::ZERO
+    }
+
+    fn one() -> Self {
+        Curve25519Scalar::ONE
+    }
+
+    fn from_u64(n: u64) -> Self {
+        Curve25519Scalar::from(n)
+    }
+
+    fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        let mut bytes = [0u8; 64];
+        rng.fill(&mut bytes[..]);
+        Curve25519Scalar::from_bytes_mod_order_wide(&bytes)
+    }
+
+    fn inverse(&self) -> Self {
+        self.invert()
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        self.as_bytes().to_vec()
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let bytes: [u8; 32] = bytes.try_into().ok()?;
+        Curve25519Scalar::from_canonical_bytes(bytes).into()
+    }
+}
+
+impl Point for RistrettoPoint {
+    type Scalar = Curve25519Scalar;
+    type Curve = Ristretto255;
+
+    fn generator() -> Self {
+        RISTRETTO_BASEPOINT_POINT
+    }
+
+    fn mul_generator(scalar: &Curve25519Scalar) -> Self {
+        RistrettoPoint::mul_base(scalar)
+    }
+
+    fn identity() -> Self {
+        <RistrettoPoint as Identity>::identity()
+    }
+
+    /// Maps `SHA512(data)` to the group with the Ristretto Elligator map.
+    fn hash_to_curve(data: &[u8]) -> Self {
+        let mut bytes = [0u8; 64];
+        bytes.copy_from_slice(&Sha512::digest(data));
+        RistrettoPoint::from_uniform_bytes(&bytes)
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        self.compress().as_bytes().to_vec()
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        CompressedRistretto::from_slice(bytes).ok()?.decompress()
+    }
+}
+
+impl Point for EdwardsPoint {
+    type Scalar = Curve25519Scalar;
+    type Curve = Ed25519;
+
+    fn generator() -> Self {
+        ED25519_BASEPOINT_POINT
+    }
+
+    fn mul_generator(scalar: &Curve25519Scalar) -> Self {
+        EdwardsPoint::mul_base(scalar)
+    }
+
+    fn identity() -> Self {
+        <EdwardsPoint as Identity>::identity()
+    }
+
+    /// Uses try-and-increment: `SHA512(data || counter)` is decompressed as a
+    /// y coordinate and multiplied by the cofactor until it lands in the
+    /// prime order subgroup with a point other than the identity.
+    fn hash_to_curve(data: &[u8]) -> Self {
+        (0u32..)
+            .find_map(|counter| {
+                let mut hasher = Sha512::new();
+                hasher.update(data);
+                hasher.update(counter.to_be_bytes());
+                let compressed = CompressedEdwardsY::from_slice(&hasher.finalize()[..32]).ok()?;
+                let point = compressed.decompress()?.mul_by_cofactor();
+                if point.is_identity() {
+                    None
+                } else {
+                    Some(point)
+                }
+            })
+            .unwrap()
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        self.compress().as_bytes().to_vec()
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let compressed = CompressedEdwardsY::from_slice(bytes).ok()?;
+        let point = compressed.decompress()?;
+        if point.compress() != compressed || !point.is_torsion_free() {
+            return None;
+        }
+        Some(point)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curve::tests::{encoding_round_trips, split_verify_recover};
+    use crate::feldman_vss::VerifiableSecretSharing;
+    use crate::pedersen_vss::PedersenVss;
+    use ed25519_dalek::hazmat::{raw_sign, ExpandedSecretKey};
+    use ed25519_dalek::{SigningKey, Verifier};
+    use rand::thread_rng;
+
+    fn pedersen_works<C: Curve>(curve: C) {
+        let secret = C::Scalar::random(&mut thread_rng());
+        let vss = PedersenVss {
+            threshold: 2,
+            share_amount: 4,
+            curve,
+        };
+        let (shares, commitments) = vss.split(&secret);
+        for share in shares.iter() {
+            assert!(PedersenVss::verify(*share, &commitments));
+        }
+        let (index, share, blinding_share) = shares[0];
+        let forged = (index, share, blinding_share + C::Scalar::one());
+        assert!(!PedersenVss::verify(forged, &commitments));
+        assert_eq!(vss.recover(&shares[1..3]), secret);
+    }
+
+    #[test]
+    fn test_ristretto255_backend() {
+        split_verify_recover(Ristretto255);
+        encoding_round_trips::<Ristretto255>();
+        pedersen_works(Ristretto255);
+    }
+
+    #[test]
+    fn test_ed25519_backend() {
+        split_verify_recover(Ed25519);
+        encoding_round_trips::<Ed25519>();
+        pedersen_works(Ed25519);
+    }
+
+    #[test]
+    fn test_ed25519_rejects_torsion_points() {
+        // y = -1 encodes the point of order 2
+        let mut order_two = [0xffu8; 32];
+        order_two[0] = 0xec;
+        order_two[31] = 0x7f;
+        assert!(CompressedEdwardsY(order_two).decompress().is_some());
+        assert_eq!(<EdwardsPoint as Point>::from_bytes(&order_two), None);
+    }
+
+    #[test]
+    fn test_ed25519_recovered_key_signs() {
+        let mut seed = [0u8; 32];
+        thread_rng().fill(&mut seed[..]);
+        let signing_key = SigningKey::from_bytes(&seed);
+        let verifying_key = signing_key.verifying_key();
+        let vss = VerifiableSecretSharing {
+            threshold: 3,
+            share_amount: 5,
+            curve: Ed25519,
+        };
+        let (shares, commitments) = vss.split(&signing_key.to_scalar());
+        assert_eq!(
+            commitments[0].compress().to_bytes(),
+            verifying_key.to_bytes()
+        );
+        for share in shares.iter() {
+            assert!(VerifiableSecretSharing::verify(*share, &commitments));
+        }
+
+        let recovered = vss.recover(&shares[1..4]);
+        let expanded = ExpandedSecretKey {
+            scalar: recovered,
+            hash_prefix: [7u8; 32],
+        };
+        let message = b"signed by a recovered key";
+        let signature = raw_sign::<sha2_010::Sha512>(&expanded, message, &verifying_key);
+        assert!(verifying_key.verify(message, &signature).is_ok());
+        assert!(verifying_key.verify_strict(message, &signature).is_ok());
+    }
+}
@@ -0,0 +1,497 @@
+use crate::curve::Secp256k1;
+use crate::errors::VssError;
+use crate::feldman_vss::VerifiableSecretSharing;
+use crate::secp256k1_helper::{Secp256k1Point, Secp256k1Scalar};
+use rand::rngs::StdRng;
+use rand::{thread_rng, Rng, SeedableRng};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// The commitments every dealer broadcasts in the first round.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct DealerBroadcast {
+    /// the index of the dealer.
+    pub dealer: usize,
+    /// the Feldman commitments of the dealer's polynomial.
+    pub commitments: Vec<Secp256k1Point>,
+}
+
+/// A complaint filed by `accuser` whose share from `dealer` did not verify.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Complaint {
+    /// the index of the participant complaining.
+    pub accuser: usize,
+    /// the index of the dealer complained against.
+    pub dealer: usize,
+}
+
+/// A dealer's public answer to a complaint, revealing the disputed share.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct ComplaintAnswer {
+    /// the index of the dealer answering.
+    pub dealer: usize,
+    /// the index of the participant who complained.
+    pub accuser: usize,
+    /// the disputed share `f_dealer(accuser)`.
+    pub share: Secp256k1Scalar,
+}
+
+/// The result of the key generation for one participant.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct DkgOutput {
+    /// the index of the participant.
+    pub index: usize,
+    /// the long-term secret share `x_i`.
+    pub share: Secp256k1Scalar,
+    /// the group public key `g^x`.
+    pub group_public_key: Secp256k1Point,
+    /// the summed commitments of the qualified dealers; every final share
+    /// verifies against them with `VerifiableSecretSharing::verify`.
+    pub commitments: Vec<Secp256k1Point>,
+    /// the indices of the qualified dealers.
+    pub qualified: Vec<usize>,
+}
+
+/// A participant in Pedersen's dealerless distributed key generation.
+///
+/// Every participant deals a Feldman sharing of a random secret, checks the
+/// shares dealt to it and complains about bad dealers. The group secret is the
+/// sum of the qualified dealers' secrets, which nobody ever learns.
+pub struct DkgParticipant {
+    /// the index of the participant, from `1` to `share_amount`.
+    pub index: usize,
+    vss: VerifiableSecretSharing,
+    shares: Vec<(usize, Secp256k1Scalar)>,
+    commitments: Vec<Secp256k1Point>,
+    broadcasts: BTreeMap<usize, Vec<Secp256k1Point>>,
+    received: BTreeMap<usize, Secp256k1Scalar>,
+}
+
+impl DkgParticipant {
+    /// Create a participant and deal its random secret.
+    pub fn new(
+        index: usize,
+        threshold: usize,
+        share_amount: usize,
+    ) -> Result<DkgParticipant, VssError> {
+        Self::new_with_rng(index, threshold, share_amount, &mut thread_rng())
+    }
+
+    /// Create a participant and deal its random secret, sampled from `rng`.
+    ///
+    /// Fails on an index outside `1..=share_amount`.
+    pub fn new_with_rng<R: Rng + ?Sized>(
+        index: usize,
+        threshold: usize,
+        share_amount: usize,
+        rng: &mut R,
+    ) -> Result<DkgParticipant, VssError> {
+        if index == 0 {
+            return Err(VssError::ZeroShareIndex);
+        }
+        if index > share_amount {
+            return Err(VssError::ShareIndexOutOfRange {
+                index,
+                share_amount,
+            });
+        }
+        let vss = VerifiableSecretSharing {
+            threshold,
+            share_amount,
+            curve: Secp256k1,
+        };
+        let secret = Secp256k1Scalar::random(rng);
+        let (shares, commitments) = vss.try_split_with_rng(&secret, rng)?;
+        Ok(DkgParticipant {
+            index,
+            vss,
+            shares,
+            commitments,
+            broadcasts: BTreeMap::new(),
+            received: BTreeMap::new(),
+        })
+    }
+
+    /// The commitments to broadcast to every participant.
+    pub fn broadcast(&self) -> DealerBroadcast {
+        DealerBroadcast {
+            dealer: self.index,
+            commitments: self.commitments.clone(),
+        }
+    }
+
+    /// The share to send privately to `recipient`, or `None` for an index
+    /// outside `1..=share_amount`.
+    pub fn share_for(&self, recipient: usize) -> Option<Secp256k1Scalar> {
+        let position = recipient.checked_sub(1)?;
+        self.shares.get(position).map(|(_, share)| *share)
+    }
+
+    /// Receive a dealer's broadcast and private share, complaining if the share is invalid.
+    pub fn receive(
+        &mut self,
+        broadcast: &DealerBroadcast,
+        share: Secp256k1Scalar,
+    ) -> Option<Complaint> {
+        self.broadcasts
+            .insert(broadcast.dealer, broadcast.commitments.clone());
+        let valid = broadcast.commitments.len() == self.vss.threshold
+            && VerifiableSecretSharing::try_verify((self.index, share), &broadcast.commitments)
+                .unwrap_or(false);
+        if valid {
+            self.received.insert(broadcast.dealer, share);
+            None
+        } else {
+            Some(Complaint {
+                accuser: self.index,
+                dealer: broadcast.dealer,
+            })
+        }
+    }
+
+    /// Answer a complaint against this participant by revealing the disputed share.
+    pub fn answer(&self, complaint: &Complaint) -> Option<ComplaintAnswer> {
+        if complaint.dealer != self.index || complaint.accuser == 0 {
+            return None;
+        }
+        self.shares
+            .get(complaint.accuser - 1)
+            .map(|(_, share)| ComplaintAnswer {
+                dealer: self.index,
+                accuser: complaint.accuser,
+                share: *share,
+            })
+    }
+
+    /// Adopt the shares revealed in answers to this participant's complaints.
+    pub fn apply_answers(&mut self, answers: &[ComplaintAnswer]) {
+        let index = self.index;
+        for answer in answers.iter().filter(|a| a.accuser == index) {
+            if let Some(commitments) = self.broadcasts.get(&answer.dealer) {
+                if VerifiableSecretSharing::try_verify((self.index, answer.share), commitments)
+                    .unwrap_or(false)
+                {
+                    self.received.insert(answer.dealer, answer.share);
+                }
+            }
+        }
+    }
+
+    /// Combine the shares of the qualified dealers into the long-term share.
+    pub fn finalize(&self, qualified: &[usize]) -> Result<DkgOutput, VssError> {
+        if qualified.is_empty() {
+            return Err(VssError::TooFewShares {
+                required: 1,
+                provided: 0,
+            });
+        }
+        let mut share = Secp256k1Scalar::zero();
+        let mut commitments: Option<Vec<Secp256k1Point>> = None;
+        for dealer in qualified {
+            let received = self
+                .received
+                .get(dealer)
+                .ok_or(VssError::MissingDealerShare(*dealer))?;
+            let dealer_commitments = self
+                .broadcasts
+                .get(dealer)
+                .ok_or(VssError::MissingDealerShare(*dealer))?;
+            share = share + *received;
+            commitments = Some(match commitments {
+                None => dealer_commitments.clone(),
+                Some(sum) => sum
+                    .iter()
+                    .zip(dealer_commitments.iter())
+                    .map(|(a, b)| *a + *b)
+                    .collect(),
+            });
+        }
+        let commitments = commitments.unwrap();
+        Ok(DkgOutput {
+            index: self.index,
+            share,
+            group_public_key: commitments[0],
+            commitments,
+            qualified: qualified.to_vec(),
+        })
+    }
+}
+
+/// Compute the qualified dealers from the public transcript of the protocol.
+///
+/// A dealer is disqualified if its commitments have the wrong degree, if
+/// `threshold` or more participants complained about it, or if it failed to
+/// answer a complaint with a share that verifies. Only complaints from
+/// accusers in `1..=share_amount` count, each accuser once.
+pub fn qualified_dealers(
+    threshold: usize,
+    share_amount: usize,
+    broadcasts: &[DealerBroadcast],
+    complaints: &[Complaint],
+    answers: &[ComplaintAnswer],
+) -> Vec<usize> {
+    broadcasts
+        .iter()
+        .filter(|broadcast| broadcast.commitments.len() == threshold)
+        .filter(|broadcast| {
+            survives_complaints(
+                threshold,
+                share_amount,
+                broadcast.dealer,
+                complaints,
+                |accuser| {
+                    answers.iter().any(|answer| {
+                        answer.dealer == broadcast.dealer
+                            && answer.accuser == accuser
+                            && VerifiableSecretSharing::try_verify(
+                                (answer.accuser, answer.share),
+                                &broadcast.commitments,
+                            )
+                            .unwrap_or(false)
+                    })
+                },
+            )
+        })
+        .map(|broadcast| broadcast.dealer)
+        .collect()
+}
+
+/// Whether `dealer` had fewer than `threshold` distinct accusers and
+/// `answered` every one of them.
+///
+/// Complaints from the same accuser count once, so a single party cannot
+/// disqualify an honest dealer by repeating itself, and complaints from
+/// indices outside `1..=share_amount`, which nobody can answer, are dropped.
+pub(crate) fn survives_complaints<F: Fn(usize) -> bool>(
+    threshold: usize,
+    share_amount: usize,
+    dealer: usize,
+    complaints: &[Complaint],
+    answered: F,
+) -> bool {
+    let accusers: BTreeSet<usize> = complaints
+        .iter()
+        .filter(|complaint| complaint.dealer == dealer)
+        .map(|complaint| complaint.accuser)
+        .filter(|accuser| (1..=share_amount).contains(accuser))
+        .collect();
+    accusers.len() < threshold && accusers.into_iter().all(answered)
+}
+
+/// A deterministic in-memory run of the key generation among `share_amount` parties.
+///
+/// Cheaters send a corrupted share to every other party and never answer complaints.
+pub struct DkgSimulation {
+    /// the threshold of shares to recover the group secret.
+    pub threshold: usize,
+    /// the number of parties.
+    pub share_amount: usize,
+    /// the indices of the misbehaving parties.
+    pub cheaters: Vec<usize>,
+}
+
+impl DkgSimulation {
+    /// Run every round with randomness derived from `seed` and return the honest parties' outputs.
+    pub fn run(&self, seed: u64) -> Result<Vec<DkgOutput>, VssError> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut participants = (1..=self.share_amount)
+            .map(|index| {
+                DkgParticipant::new_with_rng(index, self.threshold, self.share_amount, &mut rng)
+            })
+            .collect::<Result<Vec<DkgParticipant>, VssError>>()?;
+
+        let broadcasts: Vec<DealerBroadcast> = participants.iter().map(|p| p.broadcast()).collect();
+        let mut complaints = vec![];
+        for dealer in 0..self.share_amount {
+            for recipient in 0..self.share_amount {
+                let mut share = participants[dealer]
+                    .share_for(recipient + 1)
+                    .expect("every recipient is a participant");
+                if self.is_cheater(dealer + 1) && dealer != recipient {
+                    share = share + Secp256k1Scalar::one();
+                }
+                if let Some(complaint) = participants[recipient].receive(&broadcasts[dealer], share)
+                {
+                    complaints.push(complaint);
+                }
+            }
+        }
+
+        let answers: Vec<ComplaintAnswer> = complaints
+            .iter()
+            .filter(|complaint| !self.is_cheater(complaint.dealer))
+            .filter_map(|complaint| participants[complaint.dealer - 1].answer(complaint))
+            .collect();
+        for participant in participants.iter_mut() {
+            participant.apply_answers(&answers);
+        }
+
+        let qualified = qualified_dealers(
+            self.threshold,
+            self.share_amount,
+            &broadcasts,
+            &complaints,
+            &answers,
+        );
+        participants
+            .iter()
+            .filter(|participant| !self.is_cheater(participant.index))
+            .map(|participant| participant.finalize(&qualified))
+            .collect()
+    }
+
+    fn is_cheater(&self, index: usize) -> bool {
+        self.cheaters.contains(&index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check_outputs(outputs: &[DkgOutput], threshold: usize) {
+        let group_public_key = outputs[0].group_public_key;
+        for output in outputs {
+            assert_eq!(output.group_public_key, group_public_key);
+            assert!(VerifiableSecretSharing::verify(
+                (output.index, output.share),
+                &output.commitments
+            ));
+        }
+        let vss = VerifiableSecretSharing {
+            threshold,
+            share_amount: outputs.len(),
+            curve: Secp256k1,
+        };
+        let shares: Vec<(usize, Secp256k1Scalar)> = outputs
+            .iter()
+            .map(|output| (output.index, output.share))
+            .collect();
+        let group_secret = vss.recover(&shares[..threshold]);
+        assert_eq!(Secp256k1Point::generator() * group_secret, group_public_key);
+    }
+
+    #[test]
+    fn test_dkg_3_of_5_works() {
+        let simulation = DkgSimulation {
+            threshold: 3,
+            share_amount: 5,
+            cheaters: vec![],
+        };
+        let outputs = simulation.run(7).unwrap();
+        assert_eq!(outputs.len(), 5);
+        assert_eq!(outputs[0].qualified, vec![1, 2, 3, 4, 5]);
+        check_outputs(&outputs, 3);
+    }
+
+    #[test]
+    fn test_dkg_is_deterministic() {
+        let simulation = DkgSimulation {
+            threshold: 2,
+            share_amount: 3,
+            cheaters: vec![],
+        };
+        assert_eq!(simulation.run(42).unwrap(), simulation.run(42).unwrap());
+        assert_ne!(
+            simulation.run(42).unwrap()[0].group_public_key,
+            simulation.run(43).unwrap()[0].group_public_key
+        );
+    }
+
+    #[test]
+    fn test_dkg_disqualifies_cheater() {
+        let simulation = DkgSimulation {
+            threshold: 3,
+            share_amount: 5,
+            cheaters: vec![2],
+        };
+        let outputs = simulation.run(1).unwrap();
+        assert_eq!(outputs.len(), 4);
+        assert_eq!(outputs[0].qualified, vec![1, 3, 4, 5]);
+        check_outputs(&outputs, 3);
+    }
+
+    #[test]
+    fn test_dkg_answered_complaint_keeps_dealer() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let dealer = DkgParticipant::new_with_rng(1, 2, 2, &mut rng).unwrap();
+        let mut accuser = DkgParticipant::new_with_rng(2, 2, 2, &mut rng).unwrap();
+        let broadcast = dealer.broadcast();
+        let complaint = accuser
+            .receive(
+                &broadcast,
+                dealer.share_for(2).unwrap() + Secp256k1Scalar::one(),
+            )
+            .unwrap();
+        let answer = dealer.answer(&complaint).unwrap();
+        accuser.apply_answers(&[answer]);
+        assert_eq!(
+            qualified_dealers(2, 2, &[broadcast], &[complaint], &[answer]),
+            vec![1]
+        );
+        assert_eq!(
+            accuser.finalize(&[1]).unwrap().share,
+            dealer.share_for(2).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_dkg_rejects_out_of_range_indices() {
+        let mut rng = StdRng::seed_from_u64(4);
+        let dealer = DkgParticipant::new_with_rng(1, 2, 3, &mut rng).unwrap();
+        assert_eq!(dealer.share_for(0), None);
+        assert_eq!(dealer.share_for(4), None);
+        assert!(dealer.share_for(3).is_some());
+        assert_eq!(
+            DkgParticipant::new_with_rng(0, 2, 3, &mut rng).err(),
+            Some(VssError::ZeroShareIndex)
+        );
+        assert_eq!(
+            DkgParticipant::new_with_rng(4, 2, 3, &mut rng).err(),
+            Some(VssError::ShareIndexOutOfRange {
+                index: 4,
+                share_amount: 3
+            })
+        );
+    }
+
+    #[test]
+    fn test_dkg_repeated_and_out_of_range_complaints_do_not_disqualify() {
+        let mut rng = StdRng::seed_from_u64(4);
+        let dealer = DkgParticipant::new_with_rng(1, 2, 3, &mut rng).unwrap();
+        let broadcasts = [dealer.broadcast()];
+        // one party repeating a complaint it then gets answered, plus
+        // complaints from indices nobody holds
+        let complaint = Complaint {
+            accuser: 2,
+            dealer: 1,
+        };
+        let mut complaints = vec![complaint; 5];
+        complaints.push(Complaint {
+            accuser: 0,
+            dealer: 1,
+        });
+        complaints.push(Complaint {
+            accuser: 4,
+            dealer: 1,
+        });
+        let answer = dealer.answer(&complaint).unwrap();
+        assert_eq!(
+            qualified_dealers(2, 3, &broadcasts, &complaints, &[answer]),
+            vec![1]
+        );
+        assert_eq!(
+            qualified_dealers(2, 3, &broadcasts, &complaints, &[]),
+            Vec::<usize>::new()
+        );
+        // two distinct accusers reach the threshold
+        complaints.push(Complaint {
+            accuser: 3,
+            dealer: 1,
+        });
+        let answers = [answer, dealer.answer(&complaints[7]).unwrap()];
+        assert_eq!(
+            qualified_dealers(2, 3, &broadcasts, &complaints, &answers),
+            Vec::<usize>::new()
+        );
+    }
+}
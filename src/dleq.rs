@@ -0,0 +1,127 @@
+use crate::secp256k1_helper::{Secp256k1Point, Secp256k1Scalar};
+use crate::transcript::Transcript;
+
+/// A statement `log_g1(h1) == log_g2(h2)` proven by a `DleqProof`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct DleqStatement {
+    /// the first base.
+    pub g1: Secp256k1Point,
+    /// the first base raised to the witness.
+    pub h1: Secp256k1Point,
+    /// the second base.
+    pub g2: Secp256k1Point,
+    /// the second base raised to the witness.
+    pub h2: Secp256k1Point,
+}
+
+/// A non-interactive Chaum-Pedersen proof of equal discrete logarithms.
+///
+/// Several statements may be proven at once: they share a single challenge
+/// and carry one response each.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct DleqProof {
+    /// the Fiat-Shamir challenge `c`.
+    pub challenge: Secp256k1Scalar,
+    /// the responses `r_i = w_i - c * x_i`.
+    pub responses: Vec<Secp256k1Scalar>,
+}
+
+impl DleqProof {
+    /// Prove every statement with the witness at the same position.
+    pub fn prove(
+        transcript: &mut Transcript,
+        statements: &[DleqStatement],
+        witnesses: &[Secp256k1Scalar],
+    ) -> DleqProof {
+        assert_eq!(statements.len(), witnesses.len());
+        let nonces: Vec<Secp256k1Scalar> = statements
+            .iter()
+            .map(|_| Secp256k1Scalar::new_random())
+            .collect();
+        let announcements: Vec<(Secp256k1Point, Secp256k1Point)> = statements
+            .iter()
+            .zip(nonces.iter())
+            .map(|(statement, nonce)| (statement.g1 * *nonce, statement.g2 * *nonce))
+            .collect();
+        let challenge = Self::challenge(transcript, statements, &announcements);
+        let responses = nonces
+            .iter()
+            .zip(witnesses.iter())
+            .map(|(nonce, witness)| *nonce - challenge * *witness)
+            .collect();
+        DleqProof {
+            challenge,
+            responses,
+        }
+    }
+
+    /// Verify the proof for the given statements.
+    pub fn verify(&self, transcript: &mut Transcript, statements: &[DleqStatement]) -> bool {
+        if statements.len() != self.responses.len() {
+            return false;
+        }
+        let announcements: Vec<(Secp256k1Point, Secp256k1Point)> = statements
+            .iter()
+            .zip(self.responses.iter())
+            .map(|(statement, response)| {
+                (
+                    statement.g1 * *response + statement.h1 * self.challenge,
+                    statement.g2 * *response + statement.h2 * self.challenge,
+                )
+            })
+            .collect();
+        Self::challenge(transcript, statements, &announcements) == self.challenge
+    }
+
+    fn challenge(
+        transcript: &mut Transcript,
+        statements: &[DleqStatement],
+        announcements: &[(Secp256k1Point, Secp256k1Point)],
+    ) -> Secp256k1Scalar {
+        transcript.append_u64(b"dleq statements", statements.len() as u64);
+        for (statement, (a1, a2)) in statements.iter().zip(announcements.iter()) {
+            transcript.append_point(b"g1", &statement.g1);
+            transcript.append_point(b"h1", &statement.h1);
+            transcript.append_point(b"g2", &statement.g2);
+            transcript.append_point(b"h2", &statement.h2);
+            transcript.append_point(b"a1", a1);
+            transcript.append_point(b"a2", a2);
+        }
+        transcript.challenge_scalar(b"dleq challenge")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn statement(witness: &Secp256k1Scalar) -> DleqStatement {
+        let g1 = Secp256k1Point::generator();
+        let g2 = g1 * Secp256k1Scalar::new_random();
+        DleqStatement {
+            g1,
+            h1: g1 * *witness,
+            g2,
+            h2: g2 * *witness,
+        }
+    }
+
+    #[test]
+    fn test_dleq_batch_proof_works() {
+        let witnesses: Vec<Secp256k1Scalar> =
+            (0..3).map(|_| Secp256k1Scalar::new_random()).collect();
+        let statements: Vec<DleqStatement> = witnesses.iter().map(statement).collect();
+        let proof = DleqProof::prove(&mut Transcript::new(b"test"), &statements, &witnesses);
+        assert!(proof.verify(&mut Transcript::new(b"test"), &statements));
+        assert!(!proof.verify(&mut Transcript::new(b"other"), &statements));
+    }
+
+    #[test]
+    fn test_dleq_rejects_unequal_logs() {
+        let witness = Secp256k1Scalar::new_random();
+        let mut bad = statement(&witness);
+        bad.h2 = bad.h2 + bad.g2;
+        let proof = DleqProof::prove(&mut Transcript::new(b"test"), &[bad], &[witness]);
+        assert!(!proof.verify(&mut Transcript::new(b"test"), &[bad]));
+    }
+}
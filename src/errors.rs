@@ -0,0 +1,174 @@
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt;
+
+/// The `VssError` describes why a sharing, verification or recovery request was rejected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VssError {
+    /// the threshold is zero or larger than the share amount.
+    InvalidThreshold {
+        /// the requested threshold.
+        threshold: usize,
+        /// the requested share amount.
+        share_amount: usize,
+    },
+    /// more shares were requested than the field has distinct non-zero indices.
+    TooManyShares {
+        /// the largest supported share amount.
+        maximum: usize,
+        /// the requested share amount.
+        requested: usize,
+    },
+    /// the same share index was submitted more than once.
+    DuplicateShareIndex(usize),
+    /// a share was submitted with index `0`, which would reveal the secret.
+    ZeroShareIndex,
+    /// fewer shares than the threshold were submitted.
+    TooFewShares {
+        /// the number of shares needed.
+        required: usize,
+        /// the number of shares submitted.
+        provided: usize,
+    },
+    /// no commitments were submitted for verification.
+    EmptyCommitments,
+    /// the listed shares do not verify against the commitments. When
+    /// combining, fewer than threshold valid shares are left.
+    InvalidShares(Vec<usize>),
+    /// the listed share indices disagree with the polynomial interpolated from
+    /// the others. This only means the shares do not lie on one polynomial: a
+    /// corrupt share among those interpolated makes the honest extras
+    /// disagree, so the listed indices are not necessarily the corrupt ones.
+    InconsistentShares(Vec<usize>),
+    /// the number of participant public keys does not match the share amount.
+    WrongPublicKeyCount {
+        /// the share amount.
+        expected: usize,
+        /// the number of public keys submitted.
+        actual: usize,
+    },
+    /// no valid share could be decrypted for the given index.
+    DecryptionFailed(usize),
+    /// no valid share was received from a qualified dealer.
+    MissingDealerShare(usize),
+    /// a protocol message was handled in the wrong round.
+    UnexpectedRound,
+    /// the listed signers' signature shares are missing or do not verify.
+    InvalidSignatureShares(Vec<usize>),
+    /// the listed shareholders' partial decryptions do not verify.
+    InvalidDecryptionShares(Vec<usize>),
+    /// more shares are corrupt than the error correction can handle.
+    TooManyCorruptShares {
+        /// the number of corrupt shares that could have been corrected.
+        correctable: usize,
+    },
+    /// the byte string cannot be framed into field elements, or the recovered
+    /// field elements are not a framed byte string.
+    MalformedEncoding,
+    /// the recovered data does not authenticate under the recovered key.
+    AuthenticationFailed,
+    /// the input does not match the degree of the sharing polynomial.
+    DegreeMismatch {
+        /// the expected number of points or coefficients.
+        expected: usize,
+        /// the submitted number of points or coefficients.
+        actual: usize,
+    },
+    /// a participant index is above the share amount.
+    ShareIndexOutOfRange {
+        /// the submitted index.
+        index: usize,
+        /// the share amount, the largest valid index.
+        share_amount: usize,
+    },
+}
+
+impl fmt::Display for VssError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            VssError::InvalidThreshold {
+                threshold,
+                share_amount,
+            } => write!(
+                f,
+                "invalid threshold {} for {} shares",
+                threshold, share_amount
+            ),
+            VssError::TooManyShares { maximum, requested } => write!(
+                f,
+                "too many shares: at most {} supported, {} requested",
+                maximum, requested
+            ),
+            VssError::DuplicateShareIndex(index) => write!(f, "duplicate share index {}", index),
+            VssError::ZeroShareIndex => write!(f, "share index must not be zero"),
+            VssError::TooFewShares { required, provided } => write!(
+                f,
+                "too few shares: {} required, {} provided",
+                required, provided
+            ),
+            VssError::InconsistentShares(indices) => {
+                write!(f, "inconsistent shares at indices {:?}", indices)
+            }
+            VssError::InvalidShares(indices) => {
+                write!(
+                    f,
+                    "too few valid shares, invalid shares at indices {:?}",
+                    indices
+                )
+            }
+            VssError::EmptyCommitments => write!(f, "commitments must not be empty"),
+            VssError::WrongPublicKeyCount { expected, actual } => write!(
+                f,
+                "wrong number of public keys: expected {}, got {}",
+                expected, actual
+            ),
+            VssError::DecryptionFailed(index) => {
+                write!(f, "failed to decrypt a valid share for index {}", index)
+            }
+            VssError::MissingDealerShare(dealer) => {
+                write!(f, "no valid share received from dealer {}", dealer)
+            }
+            VssError::UnexpectedRound => write!(f, "message handled in the wrong round"),
+            VssError::InvalidSignatureShares(indices) => {
+                write!(f, "invalid signature shares from {:?}", indices)
+            }
+            VssError::InvalidDecryptionShares(indices) => {
+                write!(f, "invalid partial decryptions from {:?}", indices)
+            }
+            VssError::TooManyCorruptShares { correctable } => write!(
+                f,
+                "too many corrupt shares: at most {} can be corrected",
+                correctable
+            ),
+            VssError::MalformedEncoding => write!(f, "malformed byte string encoding"),
+            VssError::AuthenticationFailed => write!(f, "recovered data failed to authenticate"),
+            VssError::DegreeMismatch { expected, actual } => {
+                write!(f, "degree mismatch: expected {}, got {}", expected, actual)
+            }
+            VssError::ShareIndexOutOfRange {
+                index,
+                share_amount,
+            } => write!(
+                f,
+                "share index {} out of range for {} shares",
+                index, share_amount
+            ),
+        }
+    }
+}
+
+impl Error for VssError {}
+
+/// Check that no share index is zero or repeated.
+pub(crate) fn check_share_indices<T>(shares: &[(usize, T)]) -> Result<(), VssError> {
+    let mut seen = HashSet::new();
+    for (index, _) in shares {
+        if *index == 0 {
+            return Err(VssError::ZeroShareIndex);
+        }
+        if !seen.insert(*index) {
+            return Err(VssError::DuplicateShareIndex(*index));
+        }
+    }
+    Ok(())
+}
@@ -1,156 +1,325 @@
-use crate::secp256k1_helper::{Secp256k1Point, Secp256k1Scalar};
-use num_bigint_dig::BigInt;
-/// The `VerifiableSecretSharing` stores.
-pub struct VerifiableSecretSharing {
+use crate::curve::{Curve, Point, Scalar, Secp256k1};
+use crate::errors::{check_share_indices, VssError};
+use crate::field::{self, ScalarField};
+use rand::{thread_rng, Rng};
+
+type SharesAndCommitments<C> = (Vec<(usize, <C as Curve>::Scalar)>, Vec<<C as Curve>::Point>);
+
+/// The `VerifiableSecretSharing` stores the parameters of Feldman's verifiable
+/// secret sharing over the curve `C`, secp256k1 by default.
+pub struct VerifiableSecretSharing<C: Curve = Secp256k1> {
     /// the threshold of shares to recover the secret.
     pub threshold: usize,
     /// the total number of shares to generate from the secret.
     pub share_amount: usize,
+    /// the curve of the shares and commitments.
+    pub curve: C,
 }
 
-impl VerifiableSecretSharing {
+impl<C: Curve> VerifiableSecretSharing<C> {
     /// Split the secret to shares and commitments.
     ///
-    /// Shares are represented as Secp256k1Scalar, which is 32 bytes slice.
-    /// Commitment are represented as Secp256k1Point
-    pub fn split(
+    /// Shares are represented as scalars of the curve, e.g. `Secp256k1Scalar`.
+    /// Commitment are represented as points of the curve, e.g. `Secp256k1Point`
+    ///
+    /// Panics if the threshold is invalid, see `try_split`.
+    pub fn split(&self, secret: &C::Scalar) -> SharesAndCommitments<C> {
+        self.try_split(secret).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Split the secret to shares and commitments, failing on an invalid threshold.
+    pub fn try_split(&self, secret: &C::Scalar) -> Result<SharesAndCommitments<C>, VssError> {
+        self.try_split_with_rng(secret, &mut thread_rng())
+    }
+
+    /// Split the secret to shares and commitments, sampling the polynomial from `rng`.
+    pub fn try_split_with_rng<R: Rng + ?Sized>(
         &self,
-        secret: &Secp256k1Scalar,
-    ) -> (Vec<(usize, Secp256k1Scalar)>, Vec<Secp256k1Point>) {
-        assert!(self.threshold <= self.share_amount);
-        let polynomial = self.sample_polynomial(secret);
+        secret: &C::Scalar,
+        rng: &mut R,
+    ) -> Result<SharesAndCommitments<C>, VssError> {
+        if self.threshold == 0 || self.threshold > self.share_amount {
+            return Err(VssError::InvalidThreshold {
+                threshold: self.threshold,
+                share_amount: self.share_amount,
+            });
+        }
+        let polynomial = self.sample_polynomial_with_rng(secret, rng);
         let shares = self.evaluate_polynomial(&polynomial);
-        println!("polynomial: {:?}", polynomial);
-        let commitments = Self::generate_commitments(&polynomial);
-        (shares, commitments)
+        let commitments: Vec<C::Point> = VerifiableSecretSharing::generate_commitments(&polynomial);
+        Ok((shares, commitments))
     }
 
-    /// Recover the secret by threshold+1 shares.
-    pub fn recover(&self, shares: &[(usize, Secp256k1Scalar)]) -> Secp256k1Scalar {
-        assert!(shares.len() == self.threshold);
-        let (xs, ys): (Vec<usize>, Vec<Secp256k1Scalar>) = shares.iter().cloned().unzip();
-        self.lagrange_interpolation(Secp256k1Scalar::zero(), &xs, &ys)
+    /// Recover the secret by at least threshold shares.
+    ///
+    /// Panics if the shares are invalid or inconsistent, see `try_recover`.
+    pub fn recover(&self, shares: &[(usize, C::Scalar)]) -> C::Scalar {
+        self.try_recover(shares).unwrap_or_else(|e| panic!("{}", e))
     }
 
-    /// Verify a specific share distributed by the dealer is valid.
-    pub fn verify(share: (usize, Secp256k1Scalar), commitments: &[Secp256k1Point]) -> bool {
-        let generator = Secp256k1Point::generator();
-        let (share_index, share_value) = share;
-        let share_value_commitment = generator * share_value;
-        let share_index_scalar = Secp256k1Scalar::from_bigint(&BigInt::from(share_index));
-        let mut commitments_iter_rev = commitments.iter().rev();
-        let commitments_head = commitments_iter_rev.next().unwrap();
-        // println!(
-        //     "share_index_scalar:{:?}, share_value:{:?}, commitments: {:?}",
-        //     share_index_scalar, share_value, commitments
-        // );
-        let share_index_commitment = commitments_iter_rev.fold(*commitments_head, |sum, item| {
-            // println!(
-            //     "sum * share_index_scalar + *item: {:?} * {:?} + {:?}",
-            //     sum, share_index_scalar, *item
-            // );
-            sum * share_index_scalar + *item
-        });
-        // println!(
-        //     "share_value_commitment: {:?} \n
-        //     share_index_commitment:{:?} \n
-        //     manual_value_commitment: {:?} \n
-        //     manual_a0_commitment: {:?}, \n
-        //     manual_a1_commitment: {:?}, \n
-        //     manual_index_commitment: {:?}",
-        //     share_value_commitment,
-        //     share_index_commitment,
-        //     generator * Secp256k1Scalar::from_bigint(&BigInt::from(11)),
-        //     generator * Secp256k1Scalar::from_bigint(&BigInt::from(5)),
-        //     generator * Secp256k1Scalar::from_bigint(&BigInt::from(3)),
-        //     generator
-        //         * Secp256k1Scalar::from_bigint(&BigInt::from(3))
-        //         * Secp256k1Scalar::from_bigint(&BigInt::from(2))
-        //         + generator * Secp256k1Scalar::from_bigint(&BigInt::from(5)),
-        // );
-        share_value_commitment == share_index_commitment
-    }
-
-    fn generate_commitments(polynomial: &[Secp256k1Scalar]) -> Vec<Secp256k1Point> {
-        let generator: Secp256k1Point = Secp256k1Point::generator();
-        let len = polynomial.len();
-        (0..len).map(|i| generator * polynomial[i]).collect()
-    }
-
-    fn sample_polynomial(&self, secret: &Secp256k1Scalar) -> Vec<Secp256k1Scalar> {
-        let mut coefficients = vec![*secret];
-        let random_coefficients: Vec<Secp256k1Scalar> = (0..(self.threshold - 1))
-            .map(|_| Secp256k1Scalar::new_random())
+    /// Recover the secret by at least threshold shares.
+    ///
+    /// The secret is interpolated from the first threshold shares, every extra
+    /// share is then checked against that polynomial and the indices of those
+    /// which disagree are reported as `VssError::InconsistentShares`, which
+    /// does not necessarily name the corrupt shares; `recover_verified`
+    /// excludes the shares failing verification.
+    pub fn try_recover(&self, shares: &[(usize, C::Scalar)]) -> Result<C::Scalar, VssError> {
+        if self.threshold == 0 || shares.len() < self.threshold {
+            return Err(VssError::TooFewShares {
+                required: self.threshold,
+                provided: shares.len(),
+            });
+        }
+        check_share_indices(shares)?;
+        let (xs, ys): (Vec<usize>, Vec<C::Scalar>) =
+            shares[..self.threshold].iter().cloned().unzip();
+        let inconsistent: Vec<usize> = shares[self.threshold..]
+            .iter()
+            .filter(|(x, y)| {
+                self.lagrange_interpolation(C::Scalar::from_u64(*x as u64), &xs, &ys) != *y
+            })
+            .map(|(x, _)| *x)
             .collect();
-        coefficients.extend(random_coefficients);
-        coefficients
+        if !inconsistent.is_empty() {
+            return Err(VssError::InconsistentShares(inconsistent));
+        }
+        Ok(self.lagrange_interpolation(C::Scalar::zero(), &xs, &ys))
     }
 
-    fn evaluate_polynomial(&self, polynomial: &[Secp256k1Scalar]) -> Vec<(usize, Secp256k1Scalar)> {
+    /// Recover the secret from the shares which verify against the commitments,
+    /// returning it with the indices of the shares which do not.
+    ///
+    /// Fails with `VssError::DegreeMismatch` if there are not threshold
+    /// commitments, and with `VssError::InvalidShares` listing the offending
+    /// indices if fewer than threshold shares verify.
+    pub fn recover_verified(
+        &self,
+        shares: &[(usize, C::Scalar)],
+        commitments: &[C::Point],
+    ) -> Result<(C::Scalar, Vec<usize>), VssError> {
+        if commitments.len() != self.threshold {
+            return Err(VssError::DegreeMismatch {
+                expected: self.threshold,
+                actual: commitments.len(),
+            });
+        }
+        check_share_indices(shares)?;
+        let invalid = VerifiableSecretSharing::verify_batch(shares, commitments)?;
+        let valid: Vec<(usize, C::Scalar)> = shares
+            .iter()
+            .filter(|(x, _)| !invalid.contains(x))
+            .cloned()
+            .collect();
+        if valid.len() < self.threshold {
+            return Err(VssError::InvalidShares(invalid));
+        }
+        Ok((self.try_recover(&valid)?, invalid))
+    }
+
+    pub(crate) fn sample_polynomial_with_rng<R: Rng + ?Sized>(
+        &self,
+        secret: &C::Scalar,
+        rng: &mut R,
+    ) -> Vec<C::Scalar> {
+        field::sample_polynomial(&ScalarField::new(), secret, self.threshold, rng)
+    }
+
+    pub(crate) fn evaluate_polynomial(&self, polynomial: &[C::Scalar]) -> Vec<(usize, C::Scalar)> {
         (1..=self.share_amount)
             .map(|x| (x, self.mod_evaluate_at(polynomial, x)))
             .collect()
     }
 
-    fn mod_evaluate_at(&self, polynomial: &[Secp256k1Scalar], x: usize) -> Secp256k1Scalar {
-        let scalar_x: Secp256k1Scalar = Secp256k1Scalar::from_bigint(&BigInt::from(x));
-        polynomial
+    pub(crate) fn mod_evaluate_at(&self, polynomial: &[C::Scalar], x: usize) -> C::Scalar {
+        field::evaluate_polynomial(
+            &ScalarField::new(),
+            polynomial,
+            &C::Scalar::from_u64(x as u64),
+        )
+    }
+
+    fn lagrange_interpolation(&self, x: C::Scalar, xs: &[usize], ys: &[C::Scalar]) -> C::Scalar {
+        field::lagrange_interpolation(&ScalarField::new(), &x, xs, ys)
+    }
+}
+
+impl VerifiableSecretSharing {
+    /// Create the parameters of a sharing over secp256k1.
+    pub fn new(threshold: usize, share_amount: usize) -> Self {
+        VerifiableSecretSharing {
+            threshold,
+            share_amount,
+            curve: Secp256k1,
+        }
+    }
+}
+
+/// The associated functions take the points of the curve `C`, which is
+/// inferred from the point type of the arguments through `Point::Curve`.
+impl<C: Curve> VerifiableSecretSharing<C> {
+    /// Verify a specific share distributed by the dealer is valid.
+    ///
+    /// Malformed input, a zero share index or empty commitments, does not
+    /// verify; `try_verify` tells it apart from an invalid share.
+    pub fn verify<P: Point<Curve = C>>(share: (usize, P::Scalar), commitments: &[P]) -> bool {
+        Self::try_verify(share, commitments).unwrap_or(false)
+    }
+
+    /// Verify a specific share distributed by the dealer is valid,
+    /// failing on a zero share index or empty commitments.
+    pub fn try_verify<P: Point<Curve = C>>(
+        share: (usize, P::Scalar),
+        commitments: &[P],
+    ) -> Result<bool, VssError> {
+        let (share_index, share_value) = share;
+        let share_index_commitment = Self::evaluate_commitments(commitments, share_index)?;
+        let share_value_commitment = P::mul_generator(&share_value);
+        Ok(share_value_commitment == share_index_commitment)
+    }
+
+    /// Verify many shares against the same commitments at once, returning the
+    /// indices of the shares which do not verify, see `verify_batch_with_rng`.
+    pub fn verify_batch<P: Point<Curve = C>>(
+        shares: &[(usize, P::Scalar)],
+        commitments: &[P],
+    ) -> Result<Vec<usize>, VssError> {
+        Self::verify_batch_with_rng(shares, commitments, &mut thread_rng())
+    }
+
+    /// Verify many shares against the same commitments at once, returning the
+    /// indices of the shares which do not verify, failing on a zero share
+    /// index or empty commitments.
+    ///
+    /// The verification equations are combined with random weights `r_j`
+    /// from `rng` into the single check
+    /// `g * sum r_j s_j == sum C_i * (sum r_j x_j^i)`, one multi-scalar
+    /// multiplication instead of one per share. Only if it fails are the
+    /// shares checked one by one to locate the invalid ones.
+    pub fn verify_batch_with_rng<P: Point<Curve = C>, R: Rng + ?Sized>(
+        shares: &[(usize, P::Scalar)],
+        commitments: &[P],
+        rng: &mut R,
+    ) -> Result<Vec<usize>, VssError> {
+        if commitments.is_empty() {
+            return Err(VssError::EmptyCommitments);
+        }
+        if shares.iter().any(|(index, _)| *index == 0) {
+            return Err(VssError::ZeroShareIndex);
+        }
+        let mut share_sum = P::Scalar::zero();
+        let mut exponents = vec![P::Scalar::zero(); commitments.len()];
+        for (index, value) in shares {
+            let weight = P::Scalar::random(rng);
+            share_sum = share_sum + weight * *value;
+            let index_scalar = P::Scalar::from_u64(*index as u64);
+            let mut power = weight;
+            for exponent in exponents.iter_mut() {
+                *exponent = *exponent + power;
+                power = power * index_scalar;
+            }
+        }
+        if P::mul_generator(&share_sum) == P::multiscalar_mul(&exponents, commitments) {
+            return Ok(vec![]);
+        }
+        Ok(shares
             .iter()
-            .rev()
-            .fold(Secp256k1Scalar::zero(), |sum, item| {
-                // println!(
-                //     "scalar_x * sum + item: {:?} * {:?} + {:?}",
-                //     scalar_x, sum, *item
-                // );
-                (scalar_x * sum + *item).mod_scalar()
-            })
+            .filter(|share| !Self::verify(**share, commitments))
+            .map(|(index, _)| *index)
+            .collect())
     }
 
-    fn lagrange_interpolation(
-        &self,
-        x: Secp256k1Scalar,
-        xs: &[usize],
-        ys: &[Secp256k1Scalar],
-    ) -> Secp256k1Scalar {
-        let scalar_xs: Vec<Secp256k1Scalar> = xs
+    /// Evaluate the committed polynomial in the exponent at `index`, as one
+    /// multi-scalar multiplication of the commitments by the powers of `index`.
+    pub(crate) fn evaluate_commitments<P: Point<Curve = C>>(
+        commitments: &[P],
+        index: usize,
+    ) -> Result<P, VssError> {
+        if index == 0 {
+            return Err(VssError::ZeroShareIndex);
+        }
+        if commitments.is_empty() {
+            return Err(VssError::EmptyCommitments);
+        }
+        // sum index^i * commitments[i] as one multi-scalar multiplication
+        let index_scalar = P::Scalar::from_u64(index as u64);
+        let powers: Vec<P::Scalar> = commitments
             .iter()
-            .map(|x| Secp256k1Scalar::from_bigint(&BigInt::from(*x)))
+            .scan(P::Scalar::one(), |power, _| {
+                let current = *power;
+                *power = *power * index_scalar;
+                Some(current)
+            })
             .collect();
-        (0..self.threshold).fold(Secp256k1Scalar::zero(), |sum, item| {
-            let numerator: Secp256k1Scalar =
-                (0..self.threshold).fold(Secp256k1Scalar::one(), |product, i| {
-                    if i == item {
-                        product
-                    } else {
-                        (product * (x - scalar_xs[i])).mod_scalar()
-                    }
-                });
-            let denominator: Secp256k1Scalar =
-                (0..self.threshold).fold(Secp256k1Scalar::one(), |product, i| {
-                    if i == item {
-                        product
-                    } else {
-                        product * (scalar_xs[item] - scalar_xs[i]).mod_scalar()
-                    }
-                });
-            // println!(
-            //     "sum + num/den * ys[{:?}]: {:?} + {:?} / {:?} * {:?} ",
-            //     item, sum, numerator, denominator, ys[item]
-            // );
-            (sum + numerator * denominator.inv() * ys[item]).mod_scalar()
-        })
+        Ok(P::multiscalar_mul(&powers, commitments))
+    }
+
+    pub(crate) fn generate_commitments<P: Point<Curve = C>>(polynomial: &[P::Scalar]) -> Vec<P> {
+        polynomial.iter().map(P::mul_generator).collect()
+    }
+
+    /// Compute the Lagrange basis polynomials of the points `xs`, evaluated at `x`.
+    pub(crate) fn lagrange_coefficients(x: C::Scalar, xs: &[usize]) -> Vec<C::Scalar> {
+        field::lagrange_coefficients(&ScalarField::new(), &x, xs)
+    }
+
+    /// Check every share of a threshold protocol with `is_valid` and
+    /// interpolate threshold of the valid ones at zero in the exponent.
+    ///
+    /// `share` maps a protocol share to its index and group element. Shares
+    /// that do not verify are dropped; the error built by `invalid` lists
+    /// them only if fewer than threshold valid ones remain. The threshold is
+    /// the number of commitments, so zero fails with `EmptyCommitments`.
+    pub(crate) fn combine_valid_shares<T, P: Point<Curve = C>>(
+        threshold: usize,
+        shares: &[T],
+        share: impl Fn(&T) -> (usize, P),
+        mut is_valid: impl FnMut(&T) -> Result<bool, VssError>,
+        invalid: impl FnOnce(Vec<usize>) -> VssError,
+    ) -> Result<P, VssError> {
+        if threshold == 0 {
+            return Err(VssError::EmptyCommitments);
+        }
+        if shares.len() < threshold {
+            return Err(VssError::TooFewShares {
+                required: threshold,
+                provided: shares.len(),
+            });
+        }
+        let points: Vec<(usize, P)> = shares.iter().map(share).collect();
+        check_share_indices(&points)?;
+        let mut invalid_indices = vec![];
+        let mut valid = vec![];
+        for (candidate, point) in shares.iter().zip(points) {
+            if is_valid(candidate)? {
+                valid.push(point);
+            } else {
+                invalid_indices.push(point.0);
+            }
+        }
+        if valid.len() < threshold {
+            return Err(invalid(invalid_indices));
+        }
+
+        let (xs, values): (Vec<usize>, Vec<P>) = valid[..threshold].iter().copied().unzip();
+        let coefficients =
+            field::lagrange_coefficients(&ScalarField::new(), &P::Scalar::zero(), &xs);
+        Ok(P::multiscalar_mul(&coefficients, &values))
     }
 }
 
 #[cfg(test)]
+#[allow(clippy::needless_borrow)]
 mod tests {
     use super::*;
+    use crate::secp256k1_helper::{Secp256k1Point, Secp256k1Scalar};
     #[test]
     fn test_vss_3_of_5_works() {
         let secret: Secp256k1Scalar = Secp256k1Scalar::new_random();
         let vss = VerifiableSecretSharing {
             threshold: 3,
             share_amount: 5,
+            curve: Secp256k1,
         };
         let (shares, commitments) = vss.split(&secret);
         let sub_shares = &shares[0..3];
@@ -167,6 +336,7 @@ mod tests {
         let vss = VerifiableSecretSharing {
             threshold: 2,
             share_amount: 2,
+            curve: Secp256k1,
         };
         let (shares, commitments) = vss.split(&secret);
         println!("shares: {:?}", shares);
@@ -191,13 +361,15 @@ mod tests {
         let vss = VerifiableSecretSharing {
             threshold: 2,
             share_amount: 2,
+            curve: Secp256k1,
         };
         let shares = vss.evaluate_polynomial(&polynomial);
         println!("shares: {:?}", shares);
 
         let recovered = vss.recover(&shares);
         assert_eq!(secret, recovered);
-        let commitments = VerifiableSecretSharing::generate_commitments(&polynomial);
+        let commitments: Vec<Secp256k1Point> =
+            VerifiableSecretSharing::generate_commitments(&polynomial);
         for share in shares {
             assert!(VerifiableSecretSharing::verify(share, &commitments))
         }
@@ -209,6 +381,7 @@ mod tests {
         let vss = VerifiableSecretSharing {
             threshold: 67,
             share_amount: 100,
+            curve: Secp256k1,
         };
         let (shares, commitments) = vss.split(&secret);
         let sub_shares = &shares[0..67];
@@ -218,4 +391,197 @@ mod tests {
             assert!(VerifiableSecretSharing::verify(share, &commitments))
         }
     }
+
+    #[test]
+    fn test_vss_try_split_rejects_invalid_threshold() {
+        let secret: Secp256k1Scalar = Secp256k1Scalar::new_random();
+        for (threshold, share_amount) in [(0, 5), (6, 5)].iter() {
+            let vss = VerifiableSecretSharing {
+                threshold: *threshold,
+                share_amount: *share_amount,
+                curve: Secp256k1,
+            };
+            assert_eq!(
+                vss.try_split(&secret).unwrap_err(),
+                VssError::InvalidThreshold {
+                    threshold: *threshold,
+                    share_amount: *share_amount
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn test_vss_try_recover_rejects_bad_shares() {
+        let secret: Secp256k1Scalar = Secp256k1Scalar::new_random();
+        let vss = VerifiableSecretSharing {
+            threshold: 3,
+            share_amount: 5,
+            curve: Secp256k1,
+        };
+        let (shares, _) = vss.try_split(&secret).unwrap();
+        assert_eq!(
+            vss.try_recover(&shares[0..2]).unwrap_err(),
+            VssError::TooFewShares {
+                required: 3,
+                provided: 2
+            }
+        );
+        let duplicated = [shares[0], shares[1], shares[0]];
+        assert_eq!(
+            vss.try_recover(&duplicated).unwrap_err(),
+            VssError::DuplicateShareIndex(1)
+        );
+        let zero_index = [(0, shares[0].1), shares[1], shares[2]];
+        assert_eq!(
+            vss.try_recover(&zero_index).unwrap_err(),
+            VssError::ZeroShareIndex
+        );
+        assert_eq!(vss.try_recover(&shares[0..3]).unwrap(), secret);
+    }
+
+    #[test]
+    fn test_vss_try_verify_rejects_malformed_input() {
+        let secret: Secp256k1Scalar = Secp256k1Scalar::new_random();
+        let vss = VerifiableSecretSharing {
+            threshold: 2,
+            share_amount: 3,
+            curve: Secp256k1,
+        };
+        let (shares, commitments) = vss.try_split(&secret).unwrap();
+        assert_eq!(
+            VerifiableSecretSharing::try_verify::<Secp256k1Point>(shares[0], &[]).unwrap_err(),
+            VssError::EmptyCommitments
+        );
+        assert_eq!(
+            VerifiableSecretSharing::try_verify((0, shares[0].1), &commitments).unwrap_err(),
+            VssError::ZeroShareIndex
+        );
+        assert!(VerifiableSecretSharing::try_verify(shares[0], &commitments).unwrap());
+        let forged = (shares[0].0, shares[0].1 + Secp256k1Scalar::one());
+        assert!(!VerifiableSecretSharing::try_verify(forged, &commitments).unwrap());
+        assert!(!VerifiableSecretSharing::verify(
+            (0, shares[0].1),
+            &commitments
+        ));
+        assert!(!VerifiableSecretSharing::verify::<Secp256k1Point>(
+            shares[0],
+            &[]
+        ));
+    }
+
+    #[test]
+    fn test_vss_recover_from_superset() {
+        let secret: Secp256k1Scalar = Secp256k1Scalar::new_random();
+        let vss = VerifiableSecretSharing {
+            threshold: 3,
+            share_amount: 6,
+            curve: Secp256k1,
+        };
+        let (shares, _) = vss.split(&secret);
+        assert_eq!(vss.recover(&shares), secret);
+        assert_eq!(vss.recover(&shares[1..5]), secret);
+    }
+
+    #[test]
+    fn test_vss_recover_reports_inconsistent_shares() {
+        let secret: Secp256k1Scalar = Secp256k1Scalar::new_random();
+        let vss = VerifiableSecretSharing {
+            threshold: 3,
+            share_amount: 6,
+            curve: Secp256k1,
+        };
+        let (mut shares, _) = vss.split(&secret);
+        shares[4].1 = shares[4].1 + Secp256k1Scalar::one();
+        assert_eq!(
+            vss.try_recover(&shares).unwrap_err(),
+            VssError::InconsistentShares(vec![5])
+        );
+        // a corrupt share among the first threshold makes the extras disagree,
+        // only verification against the commitments locates it
+        let (mut shares, commitments) = vss.split(&secret);
+        shares[0].1 = shares[0].1 + Secp256k1Scalar::one();
+        assert!(vss.try_recover(&shares).is_err());
+        assert_eq!(
+            vss.recover_verified(&shares, &commitments),
+            Ok((secret, vec![1]))
+        );
+    }
+
+    #[test]
+    fn test_vss_recover_verified_excludes_invalid_shares() {
+        let secret = Secp256k1Scalar::new_random();
+        let vss = VerifiableSecretSharing {
+            threshold: 3,
+            share_amount: 6,
+            curve: Secp256k1,
+        };
+        let (mut shares, commitments) = vss.split(&secret);
+        assert_eq!(
+            vss.recover_verified(&shares, &commitments),
+            Ok((secret, vec![]))
+        );
+        shares[1].1 = shares[1].1 + Secp256k1Scalar::one();
+        shares[4].1 = Secp256k1Scalar::new_random();
+        assert_eq!(
+            vss.recover_verified(&shares, &commitments),
+            Ok((secret, vec![2, 5]))
+        );
+        assert_eq!(
+            vss.recover_verified(&shares[..5], &commitments),
+            Ok((secret, vec![2, 5]))
+        );
+        assert_eq!(
+            vss.recover_verified(&shares[1..5], &commitments),
+            Err(VssError::InvalidShares(vec![2, 5]))
+        );
+        assert_eq!(
+            vss.recover_verified(&shares, &commitments[..2]),
+            Err(VssError::DegreeMismatch {
+                expected: 3,
+                actual: 2
+            })
+        );
+    }
+
+    #[test]
+    fn test_vss_verify_batch_locates_invalid_shares() {
+        let secret = Secp256k1Scalar::new_random();
+        let vss = VerifiableSecretSharing {
+            threshold: 67,
+            share_amount: 100,
+            curve: Secp256k1,
+        };
+        let (mut shares, commitments) = vss.split(&secret);
+        assert_eq!(
+            VerifiableSecretSharing::verify_batch(&shares, &commitments),
+            Ok(vec![])
+        );
+        shares[10].1 = shares[10].1 + Secp256k1Scalar::one();
+        shares[42].1 = Secp256k1Scalar::new_random();
+        assert_eq!(
+            VerifiableSecretSharing::verify_batch(&shares, &commitments),
+            Ok(vec![11, 43])
+        );
+        assert_eq!(
+            VerifiableSecretSharing::verify_batch(&[(0, secret)], &commitments),
+            Err(VssError::ZeroShareIndex)
+        );
+        assert_eq!(
+            VerifiableSecretSharing::verify_batch::<Secp256k1Point>(&shares, &[]),
+            Err(VssError::EmptyCommitments)
+        );
+    }
+
+    #[test]
+    fn test_vss_new_shares_over_secp256k1() {
+        let secret = Secp256k1Scalar::new_random();
+        let vss = VerifiableSecretSharing::new(3, 5);
+        assert_eq!((vss.threshold, vss.share_amount), (3, 5));
+        let (shares, commitments) = vss.split(&secret);
+        assert_eq!(secret, vss.recover(&shares[1..4]));
+        for share in shares {
+            assert!(VerifiableSecretSharing::verify(share, &commitments));
+        }
+    }
 }
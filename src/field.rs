@@ -0,0 +1,667 @@
+use crate::curve::Scalar;
+use num_bigint::{BigInt, RandBigInt};
+use num_traits::{One, Zero};
+use rand::Rng;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+/// A prime field, the arithmetic shared by every secret sharing scheme of the crate.
+///
+/// Fields whose prime is only known at runtime carry it in `self`, so the
+/// operations take the field as a context instead of being operators on
+/// the elements.
+pub trait PrimeField {
+    /// the elements of the field.
+    type Element: Clone + Debug + PartialEq;
+
+    /// Return the additive identity `0`.
+    fn zero(&self) -> Self::Element;
+
+    /// Return the multiplicative identity `1`.
+    fn one(&self) -> Self::Element;
+
+    /// Map a small integer, such as a share index, into the field.
+    fn element(&self, n: u64) -> Self::Element;
+
+    /// Calculate `a + b`.
+    fn add(&self, a: &Self::Element, b: &Self::Element) -> Self::Element;
+
+    /// Calculate `a - b`.
+    fn sub(&self, a: &Self::Element, b: &Self::Element) -> Self::Element;
+
+    /// Calculate `a * b`.
+    fn mul(&self, a: &Self::Element, b: &Self::Element) -> Self::Element;
+
+    /// Calculate the multiplicative inverse, zero maps to zero.
+    fn inverse(&self, a: &Self::Element) -> Self::Element;
+
+    /// Sample a uniformly random element from the given generator.
+    fn random<R: Rng + ?Sized>(&self, rng: &mut R) -> Self::Element;
+}
+
+/// The field of integers modulo a prime chosen at runtime, as used by
+/// `ShamirSecretSharing`. Elements are kept in `[0, prime)`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BigIntField {
+    /// the characteristic of the field.
+    pub prime: BigInt,
+}
+
+impl BigIntField {
+    /// Reduce any integer, possibly negative, into `[0, prime)`.
+    pub fn normalize(&self, num: &BigInt) -> BigInt {
+        let num = num % &self.prime;
+        if num < Zero::zero() {
+            num + &self.prime
+        } else {
+            num
+        }
+    }
+
+    /**
+     * https://en.wikipedia.org/wiki/Extended_Euclidean_algorithm
+     *
+     * a*s + b*t = gcd(a,b) a > b
+     * r_0 = a*s_0 + b*t_0    s_0 = 1    t_0 = 0
+     * r_1 = a*s_1 + b*t_1    s_1 = 0    t_1 = 1
+     * r_2 = r_0 - r_1*q_1
+     *     = a(s_0 - s_1*q_1) + b(t_0 - t_1*q_1)   s_2 = s_0 - s_1*q_1     t_2 = t_0 - t_1*q_1
+     * ...
+     * stop when r_k = 0
+     */
+    fn extend_euclid_algo(&self, num: BigInt) -> (BigInt, BigInt, BigInt) {
+        let (mut r, mut next_r, mut s, mut next_s, mut t, mut next_t) = (
+            self.prime.clone(),
+            num,
+            BigInt::from(1),
+            BigInt::from(0),
+            BigInt::from(0),
+            BigInt::from(1),
+        );
+        let mut quotient;
+        let mut tmp;
+        while next_r > Zero::zero() {
+            quotient = &r / &next_r;
+            tmp = next_r.clone();
+            next_r = r - &next_r * &quotient;
+            r = tmp;
+            tmp = next_s.clone();
+            next_s = s - &next_s * &quotient;
+            s = tmp;
+            tmp = next_t.clone();
+            next_t = t - next_t * quotient;
+            t = tmp;
+        }
+        (r, s, t)
+    }
+}
+
+impl PrimeField for BigIntField {
+    type Element = BigInt;
+
+    fn zero(&self) -> BigInt {
+        Zero::zero()
+    }
+
+    fn one(&self) -> BigInt {
+        One::one()
+    }
+
+    fn element(&self, n: u64) -> BigInt {
+        self.normalize(&BigInt::from(n))
+    }
+
+    fn add(&self, a: &BigInt, b: &BigInt) -> BigInt {
+        self.normalize(&(a + b))
+    }
+
+    fn sub(&self, a: &BigInt, b: &BigInt) -> BigInt {
+        self.normalize(&(a - b))
+    }
+
+    fn mul(&self, a: &BigInt, b: &BigInt) -> BigInt {
+        self.normalize(&(a * b))
+    }
+
+    fn inverse(&self, a: &BigInt) -> BigInt {
+        // zero skips the Euclid loop and keeps t = 0, so maps to zero
+        let (_gcd, _, inv) = self.extend_euclid_algo(self.normalize(a));
+        self.normalize(&inv)
+    }
+
+    fn random<R: Rng + ?Sized>(&self, rng: &mut R) -> BigInt {
+        rng.gen_bigint_range(&Zero::zero(), &self.prime)
+    }
+}
+
+/// The fixed-width scalar field of a curve backend, such as the secp256k1
+/// group order or the 255 bits prime of Ristretto255 and BLS12-381.
+pub struct ScalarField<S: Scalar>(PhantomData<S>);
+
+impl<S: Scalar> ScalarField<S> {
+    /// Return the field of the scalars `S`.
+    pub fn new() -> ScalarField<S> {
+        ScalarField(PhantomData)
+    }
+}
+
+impl<S: Scalar> Default for ScalarField<S> {
+    fn default() -> Self {
+        ScalarField::new()
+    }
+}
+
+impl<S: Scalar> Clone for ScalarField<S> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<S: Scalar> Copy for ScalarField<S> {}
+
+impl<S: Scalar> Debug for ScalarField<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ScalarField")
+    }
+}
+
+impl<S: Scalar> PrimeField for ScalarField<S> {
+    type Element = S;
+
+    fn zero(&self) -> S {
+        S::zero()
+    }
+
+    fn one(&self) -> S {
+        S::one()
+    }
+
+    fn element(&self, n: u64) -> S {
+        S::from_u64(n)
+    }
+
+    fn add(&self, a: &S, b: &S) -> S {
+        *a + *b
+    }
+
+    fn sub(&self, a: &S, b: &S) -> S {
+        *a - *b
+    }
+
+    fn mul(&self, a: &S, b: &S) -> S {
+        *a * *b
+    }
+
+    fn inverse(&self, a: &S) -> S {
+        a.inverse()
+    }
+
+    fn random<R: Rng + ?Sized>(&self, rng: &mut R) -> S {
+        S::random(rng)
+    }
+}
+
+/// The field of integers modulo `2^256 - 189`, the largest 256 bits prime,
+/// with elements held as four little endian 64 bits limbs.
+///
+/// Unlike `BigIntField` the arithmetic is fixed width: as `2^256 = 189 mod p`,
+/// the high half of a product is folded back onto the low half times 189.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Prime256Field;
+
+/// `2^256 - 189` in little endian limbs.
+const PRIME_256: [u64; 4] = [
+    0xffff_ffff_ffff_ff43,
+    0xffff_ffff_ffff_ffff,
+    0xffff_ffff_ffff_ffff,
+    0xffff_ffff_ffff_ffff,
+];
+
+/// `2^256 mod p`, the factor folding the bits above 256 back in.
+const PRIME_256_FOLD: u64 = 189;
+
+impl Prime256Field {
+    /// Parse 32 big endian bytes, rejecting values not below the prime.
+    pub fn from_bytes(&self, bytes: &[u8; 32]) -> Option<[u64; 4]> {
+        let mut limbs = [0u64; 4];
+        for (i, limb) in limbs.iter_mut().enumerate() {
+            let mut word = [0u8; 8];
+            word.copy_from_slice(&bytes[24 - 8 * i..32 - 8 * i]);
+            *limb = u64::from_be_bytes(word);
+        }
+        if sub_limbs(&limbs, &PRIME_256).1 == 1 {
+            Some(limbs)
+        } else {
+            None
+        }
+    }
+
+    /// Serialize an element in 32 bytes big endian form.
+    pub fn to_bytes(&self, a: &[u64; 4]) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        for (i, limb) in a.iter().enumerate() {
+            bytes[24 - 8 * i..32 - 8 * i].copy_from_slice(&limb.to_be_bytes());
+        }
+        bytes
+    }
+
+    /// Reduce `high * 2^256 + a` to below the prime.
+    fn fold(a: &[u64; 4], high: u64) -> [u64; 4] {
+        let mut sum = [0u64; 4];
+        let mut carry = high as u128 * PRIME_256_FOLD as u128;
+        for i in 0..4 {
+            let wide = a[i] as u128 + carry;
+            sum[i] = wide as u64;
+            carry = wide >> 64;
+        }
+        // a carry out leaves sum below 2^72, adding 189 again cannot carry
+        let sum = add_limbs(&sum, &[carry as u64 * PRIME_256_FOLD, 0, 0, 0]).0;
+        let (difference, borrow) = sub_limbs(&sum, &PRIME_256);
+        if borrow == 1 {
+            sum
+        } else {
+            difference
+        }
+    }
+}
+
+/// `a + b` in limbs and the carry out.
+fn add_limbs(a: &[u64; 4], b: &[u64; 4]) -> ([u64; 4], u64) {
+    let mut sum = [0u64; 4];
+    let mut carry = 0u128;
+    for i in 0..4 {
+        let wide = a[i] as u128 + b[i] as u128 + carry;
+        sum[i] = wide as u64;
+        carry = wide >> 64;
+    }
+    (sum, carry as u64)
+}
+
+/// `a - b` in limbs and the borrow out.
+fn sub_limbs(a: &[u64; 4], b: &[u64; 4]) -> ([u64; 4], u64) {
+    let mut difference = [0u64; 4];
+    let mut borrow = 0u64;
+    for i in 0..4 {
+        let (partial, under) = a[i].overflowing_sub(b[i]);
+        let (result, under_borrow) = partial.overflowing_sub(borrow);
+        difference[i] = result;
+        borrow = (under | under_borrow) as u64;
+    }
+    (difference, borrow)
+}
+
+impl PrimeField for Prime256Field {
+    type Element = [u64; 4];
+
+    fn zero(&self) -> [u64; 4] {
+        [0; 4]
+    }
+
+    fn one(&self) -> [u64; 4] {
+        [1, 0, 0, 0]
+    }
+
+    fn element(&self, n: u64) -> [u64; 4] {
+        [n, 0, 0, 0]
+    }
+
+    fn add(&self, a: &[u64; 4], b: &[u64; 4]) -> [u64; 4] {
+        let (sum, carry) = add_limbs(a, b);
+        Self::fold(&sum, carry)
+    }
+
+    fn sub(&self, a: &[u64; 4], b: &[u64; 4]) -> [u64; 4] {
+        // on a borrow the difference is a - b + 2^256, subtract 189 to get a - b + p
+        let (difference, borrow) = sub_limbs(a, b);
+        sub_limbs(&difference, &[borrow * PRIME_256_FOLD, 0, 0, 0]).0
+    }
+
+    fn mul(&self, a: &[u64; 4], b: &[u64; 4]) -> [u64; 4] {
+        let mut product = [0u64; 8];
+        for i in 0..4 {
+            let mut carry = 0u128;
+            for j in 0..4 {
+                let wide = product[i + j] as u128 + a[i] as u128 * b[j] as u128 + carry;
+                product[i + j] = wide as u64;
+                carry = wide >> 64;
+            }
+            product[i + 4] = carry as u64;
+        }
+        // low + high * 189, the top limb of which is folded once more
+        let mut low = [0u64; 4];
+        let mut carry = 0u128;
+        for i in 0..4 {
+            let wide = product[i] as u128 + product[i + 4] as u128 * PRIME_256_FOLD as u128 + carry;
+            low[i] = wide as u64;
+            carry = wide >> 64;
+        }
+        Self::fold(&low, carry as u64)
+    }
+
+    fn inverse(&self, a: &[u64; 4]) -> [u64; 4] {
+        // a^(p - 2) by Fermat's little theorem, zero maps to zero
+        let exponent = sub_limbs(&PRIME_256, &[2, 0, 0, 0]).0;
+        let mut result = self.one();
+        for bit in (0..256).rev() {
+            result = self.mul(&result, &result);
+            if (exponent[bit / 64] >> (bit % 64)) & 1 == 1 {
+                result = self.mul(&result, a);
+            }
+        }
+        result
+    }
+
+    fn random<R: Rng + ?Sized>(&self, rng: &mut R) -> [u64; 4] {
+        loop {
+            let mut bytes = [0u8; 32];
+            rng.fill(&mut bytes[..]);
+            if let Some(element) = self.from_bytes(&bytes) {
+                return element;
+            }
+        }
+    }
+}
+
+/// Sample a polynomial of degree `threshold - 1` with the secret as constant term.
+pub(crate) fn sample_polynomial<F: PrimeField, R: Rng + ?Sized>(
+    field: &F,
+    secret: &F::Element,
+    threshold: usize,
+    rng: &mut R,
+) -> Vec<F::Element> {
+    let mut coefficients = vec![secret.clone()];
+    let random_coefficients: Vec<F::Element> =
+        (0..(threshold - 1)).map(|_| field.random(rng)).collect();
+    coefficients.extend(random_coefficients);
+    coefficients
+}
+
+/// Evaluate the polynomial at `x` by Horner's rule.
+pub(crate) fn evaluate_polynomial<F: PrimeField>(
+    field: &F,
+    polynomial: &[F::Element],
+    x: &F::Element,
+) -> F::Element {
+    polynomial.iter().rev().fold(field.zero(), |sum, item| {
+        field.add(&field.mul(x, &sum), item)
+    })
+}
+
+/// Compute the Lagrange basis polynomials of the points `xs`, evaluated at `x`.
+pub(crate) fn lagrange_coefficients<F: PrimeField>(
+    field: &F,
+    x: &F::Element,
+    xs: &[usize],
+) -> Vec<F::Element> {
+    let field_xs: Vec<F::Element> = xs.iter().map(|x| field.element(*x as u64)).collect();
+    (0..field_xs.len())
+        .map(|item| {
+            let (numerator, denominator) = field_xs.iter().enumerate().fold(
+                (field.one(), field.one()),
+                |(numerator, denominator), (i, x_i)| {
+                    if i == item {
+                        (numerator, denominator)
+                    } else {
+                        (
+                            field.mul(&numerator, &field.sub(x, x_i)),
+                            field.mul(&denominator, &field.sub(&field_xs[item], x_i)),
+                        )
+                    }
+                },
+            );
+            field.mul(&numerator, &field.inverse(&denominator))
+        })
+        .collect()
+}
+
+/// Interpolate the polynomial through `(xs[i], ys[i])` and evaluate it at `x`.
+pub(crate) fn lagrange_interpolation<F: PrimeField>(
+    field: &F,
+    x: &F::Element,
+    xs: &[usize],
+    ys: &[F::Element],
+) -> F::Element {
+    lagrange_coefficients(field, x, xs)
+        .iter()
+        .zip(ys.iter())
+        .fold(field.zero(), |sum, (coefficient, y)| {
+            field.add(&sum, &field.mul(coefficient, y))
+        })
+}
+
+/// Interpolate the coefficients of the polynomial of degree `xs.len() - 1`
+/// through the points `(xs[i], ys[i])`.
+pub(crate) fn interpolate_polynomial<F: PrimeField>(
+    field: &F,
+    xs: &[usize],
+    ys: &[F::Element],
+) -> Vec<F::Element> {
+    let field_xs: Vec<F::Element> = xs.iter().map(|x| field.element(*x as u64)).collect();
+    let mut coefficients = vec![field.zero(); xs.len()];
+    for (i, y) in ys.iter().enumerate() {
+        // the basis polynomial prod_{j != i} (X - x_j), lowest coefficient first
+        let mut basis = vec![field.one()];
+        let mut denominator = field.one();
+        for (j, x_j) in field_xs.iter().enumerate() {
+            if i == j {
+                continue;
+            }
+            let mut next = vec![field.zero(); basis.len() + 1];
+            for (k, coefficient) in basis.iter().enumerate() {
+                next[k + 1] = field.add(&next[k + 1], coefficient);
+                next[k] = field.sub(&next[k], &field.mul(x_j, coefficient));
+            }
+            basis = next;
+            denominator = field.mul(&denominator, &field.sub(&field_xs[i], x_j));
+        }
+        let scale = field.mul(y, &field.inverse(&denominator));
+        for (k, coefficient) in basis.iter().enumerate() {
+            coefficients[k] = field.add(&coefficients[k], &field.mul(&scale, coefficient));
+        }
+    }
+    coefficients
+}
+
+/// Decode the polynomial of degree below `threshold` through the points
+/// `(xs[i], ys[i])`, `(n - threshold) / 2` of which may be wrong, with the
+/// Berlekamp-Welch algorithm.
+///
+/// Solves `Q(x_i) = y_i * E(x_i)` for a monic error locator `E` of degree
+/// `e = (n - threshold) / 2` and `Q` of degree below `e + threshold`, then
+/// divides `Q` by `E`. Returns `None` if there are more errors than that.
+pub(crate) fn berlekamp_welch<F: PrimeField>(
+    field: &F,
+    xs: &[usize],
+    ys: &[F::Element],
+    threshold: usize,
+) -> Option<Vec<F::Element>> {
+    let errors = (xs.len() - threshold) / 2;
+    let q_len = errors + threshold;
+    let rows: Vec<Vec<F::Element>> = xs
+        .iter()
+        .zip(ys.iter())
+        .map(|(x, y)| {
+            let x = field.element(*x as u64);
+            let powers: Vec<F::Element> = (0..=q_len).fold(vec![], |mut powers, i| {
+                powers.push(if i == 0 {
+                    field.one()
+                } else {
+                    field.mul(&powers[i - 1], &x)
+                });
+                powers
+            });
+            let minus_y = field.sub(&field.zero(), y);
+            let mut row = powers[..q_len].to_vec();
+            row.extend(
+                powers[..errors]
+                    .iter()
+                    .map(|power| field.mul(&minus_y, power)),
+            );
+            row.push(field.mul(y, &powers[errors]));
+            row
+        })
+        .collect();
+    let solution = solve_linear_system(field, rows)?;
+    let q = &solution[..q_len];
+    let mut e = solution[q_len..].to_vec();
+    e.push(field.one());
+
+    // long division by the monic E, from the leading coefficient down
+    let mut remainder = q.to_vec();
+    let mut quotient = vec![field.zero(); threshold];
+    for i in (0..threshold).rev() {
+        let coefficient = remainder[i + errors].clone();
+        for (j, e_j) in e.iter().enumerate() {
+            remainder[i + j] = field.sub(&remainder[i + j], &field.mul(&coefficient, e_j));
+        }
+        quotient[i] = coefficient;
+    }
+    if remainder.iter().any(|r| *r != field.zero()) {
+        return None;
+    }
+    Some(quotient)
+}
+
+/// Solve the linear system given as augmented rows `[a_i0, .., a_im | b_i]`
+/// by Gauss-Jordan elimination, setting free variables to zero. Returns
+/// `None` if the system is inconsistent.
+fn solve_linear_system<F: PrimeField>(
+    field: &F,
+    mut rows: Vec<Vec<F::Element>>,
+) -> Option<Vec<F::Element>> {
+    let columns = rows.first().map_or(0, |row| row.len() - 1);
+    let mut pivots = vec![];
+    for column in 0..columns {
+        let rank = pivots.len();
+        let pivot = match (rank..rows.len()).find(|i| rows[*i][column] != field.zero()) {
+            Some(pivot) => pivot,
+            None => continue,
+        };
+        rows.swap(rank, pivot);
+        let inverse = field.inverse(&rows[rank][column]);
+        rows[rank] = rows[rank].iter().map(|a| field.mul(a, &inverse)).collect();
+        for i in 0..rows.len() {
+            if i != rank && rows[i][column] != field.zero() {
+                let factor = rows[i][column].clone();
+                rows[i] = rows[i]
+                    .iter()
+                    .zip(rows[rank].iter())
+                    .map(|(a, b)| field.sub(a, &field.mul(&factor, b)))
+                    .collect();
+            }
+        }
+        pivots.push(column);
+    }
+    if rows[pivots.len()..]
+        .iter()
+        .any(|row| row[columns] != field.zero())
+    {
+        return None;
+    }
+    let mut solution = vec![field.zero(); columns];
+    for (row, column) in pivots.into_iter().enumerate() {
+        solution[column] = rows[row][columns].clone();
+    }
+    Some(solution)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::secp256k1_helper::Secp256k1Scalar;
+    use num_bigint::Sign;
+    use rand::thread_rng;
+
+    fn interpolation_round_trips<F: PrimeField>(field: &F) {
+        let mut rng = thread_rng();
+        let secret = field.random(&mut rng);
+        let polynomial = sample_polynomial(field, &secret, 3, &mut rng);
+        let xs = vec![2, 4, 5];
+        let ys: Vec<F::Element> = xs
+            .iter()
+            .map(|x| evaluate_polynomial(field, &polynomial, &field.element(*x as u64)))
+            .collect();
+        assert_eq!(
+            lagrange_interpolation(field, &field.zero(), &xs, &ys),
+            secret
+        );
+        let x = field.element(7);
+        assert_eq!(
+            lagrange_interpolation(field, &x, &xs, &ys),
+            evaluate_polynomial(field, &polynomial, &x)
+        );
+        assert_eq!(interpolate_polynomial(field, &xs, &ys), polynomial);
+        let a = field.random(&mut rng);
+        if a != field.zero() {
+            assert_eq!(field.mul(&a, &field.inverse(&a)), field.one());
+        }
+    }
+
+    #[test]
+    fn test_berlekamp_welch_corrects_errors() {
+        let field = BigIntField {
+            prime: BigInt::from(1613),
+        };
+        let polynomial: Vec<BigInt> = vec![1234, 166, 94].into_iter().map(BigInt::from).collect();
+        let xs: Vec<usize> = (1..=8).collect();
+        let mut ys: Vec<BigInt> = xs
+            .iter()
+            .map(|x| evaluate_polynomial(&field, &polynomial, &field.element(*x as u64)))
+            .collect();
+        assert_eq!(
+            berlekamp_welch(&field, &xs, &ys, 3),
+            Some(polynomial.clone())
+        );
+        ys[1] = BigInt::from(7);
+        ys[6] = field.add(&ys[6], &BigInt::from(1));
+        assert_eq!(berlekamp_welch(&field, &xs, &ys, 3), Some(polynomial));
+        ys[4] = BigInt::from(0);
+        assert_eq!(berlekamp_welch(&field, &xs, &ys, 3), None);
+    }
+
+    #[test]
+    fn test_bigint_field() {
+        let field = BigIntField {
+            prime: BigInt::from(1613),
+        };
+        interpolation_round_trips(&field);
+        assert_eq!(field.inverse(&BigInt::from(-2)), BigInt::from(806));
+        assert_eq!(field.inverse(&field.zero()), field.zero());
+        assert_eq!(
+            field.sub(&BigInt::from(3), &BigInt::from(5)),
+            BigInt::from(1611)
+        );
+    }
+
+    #[test]
+    fn test_scalar_field() {
+        interpolation_round_trips(&ScalarField::<Secp256k1Scalar>::new());
+    }
+
+    #[test]
+    fn test_prime256_field_matches_bigint_field() {
+        let field = Prime256Field;
+        interpolation_round_trips(&field);
+        let reference = BigIntField {
+            prime: (BigInt::from(1) << 256) - BigInt::from(189),
+        };
+        let to_bigint = |a: &[u64; 4]| BigInt::from_bytes_be(Sign::Plus, &field.to_bytes(a));
+        let minus_one = field.sub(&field.zero(), &field.one());
+        assert_eq!(to_bigint(&minus_one), &reference.prime - BigInt::from(1));
+        assert_eq!(field.mul(&minus_one, &minus_one), field.one());
+        assert_eq!(field.add(&minus_one, &field.element(2)), field.one());
+        assert_eq!(field.inverse(&field.zero()), field.zero());
+        assert_eq!(field.from_bytes(&[0xff; 32]), None);
+
+        let mut rng = thread_rng();
+        for _ in 0..32 {
+            let (a, b) = (field.random(&mut rng), field.random(&mut rng));
+            let (x, y) = (to_bigint(&a), to_bigint(&b));
+            assert_eq!(to_bigint(&field.add(&a, &b)), reference.add(&x, &y));
+            assert_eq!(to_bigint(&field.sub(&a, &b)), reference.sub(&x, &y));
+            assert_eq!(to_bigint(&field.mul(&a, &b)), reference.mul(&x, &y));
+            assert_eq!(to_bigint(&field.inverse(&a)), reference.inverse(&x));
+            assert_eq!(field.from_bytes(&field.to_bytes(&a)), Some(a));
+        }
+    }
+}
@@ -0,0 +1,449 @@
+use crate::curve::Secp256k1;
+use crate::errors::{check_share_indices, VssError};
+use crate::feldman_vss::VerifiableSecretSharing;
+use crate::secp256k1_helper::{Secp256k1Point, Secp256k1Scalar};
+use crate::transcript::Transcript;
+use rand::{thread_rng, Rng};
+use sha2::{Digest, Sha256};
+
+/// The secret nonces of one signing session, used once and then dropped.
+#[derive(Debug)]
+pub struct SigningNonces {
+    index: usize,
+    hiding: Secp256k1Scalar,
+    binding: Secp256k1Scalar,
+}
+
+/// The public commitments `(D_i, E_i)` to a signer's nonces, sent in the first round.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct NonceCommitment {
+    /// the index of the signer.
+    pub index: usize,
+    /// the hiding nonce commitment `D_i = g^d_i`.
+    pub hiding: Secp256k1Point,
+    /// the binding nonce commitment `E_i = g^e_i`.
+    pub binding: Secp256k1Point,
+}
+
+/// The message and the nonce commitments of every signer taking part.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct SigningPackage {
+    /// the message to sign.
+    pub message: Vec<u8>,
+    /// the nonce commitments of the signers, one per signer.
+    pub commitments: Vec<NonceCommitment>,
+}
+
+/// A signer's response `z_i`, sent in the second round.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct SignatureShare {
+    /// the index of the signer.
+    pub index: usize,
+    /// the response `z_i`.
+    pub response: Secp256k1Scalar,
+}
+
+/// A BIP-340 Schnorr signature `(R.x, s)`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct SchnorrSignature {
+    /// the x coordinate of the nonce point, which has even y.
+    pub r: [u8; 32],
+    /// the response `s`.
+    pub s: Secp256k1Scalar,
+}
+
+impl SchnorrSignature {
+    /// Serialize the signature in the 64 bytes BIP-340 encoding.
+    pub fn serialize(&self) -> [u8; 64] {
+        let mut bytes = [0u8; 64];
+        bytes[..32].copy_from_slice(&self.r);
+        bytes[32..].copy_from_slice(&self.s.serialize());
+        bytes
+    }
+}
+
+/// A FROST signer holding a share from `VerifiableSecretSharing::split` or a DKG.
+///
+/// The Feldman commitments of the sharing serve as verification keys: the
+/// group key is `commitments[0]` and signer `i` is checked against
+/// `g^share_i`, evaluated from the commitments.
+pub struct FrostSigner {
+    /// the index of the share.
+    pub index: usize,
+    share: Secp256k1Scalar,
+    commitments: Vec<Secp256k1Point>,
+}
+
+impl FrostSigner {
+    /// Create a signer, checking the share against the commitments.
+    ///
+    /// Fails with `VssError::InvalidShares` naming the share's index if it
+    /// does not verify against the commitments.
+    pub fn new(
+        share: (usize, Secp256k1Scalar),
+        commitments: &[Secp256k1Point],
+    ) -> Result<FrostSigner, VssError> {
+        let (index, value) = share;
+        if !VerifiableSecretSharing::try_verify(share, commitments)? {
+            return Err(VssError::InvalidShares(vec![index]));
+        }
+        Ok(FrostSigner {
+            index,
+            share: value,
+            commitments: commitments.to_vec(),
+        })
+    }
+
+    /// Round 1: sample fresh nonces and commit to them.
+    pub fn commit(&self) -> (SigningNonces, NonceCommitment) {
+        self.commit_with_rng(&mut thread_rng())
+    }
+
+    /// Round 1: sample fresh nonces from `rng` and commit to them.
+    pub fn commit_with_rng<R: Rng + ?Sized>(
+        &self,
+        rng: &mut R,
+    ) -> (SigningNonces, NonceCommitment) {
+        let nonces = SigningNonces {
+            index: self.index,
+            hiding: Secp256k1Scalar::random(rng),
+            binding: Secp256k1Scalar::random(rng),
+        };
+        let generator = Secp256k1Point::generator();
+        let commitment = NonceCommitment {
+            index: self.index,
+            hiding: generator * nonces.hiding,
+            binding: generator * nonces.binding,
+        };
+        (nonces, commitment)
+    }
+
+    /// Round 2: compute this signer's response, consuming its nonces.
+    pub fn sign(
+        &self,
+        package: &SigningPackage,
+        nonces: SigningNonces,
+    ) -> Result<SignatureShare, VssError> {
+        let session = Session::new(package, &self.commitments)?;
+        let own = package
+            .commitments
+            .iter()
+            .find(|c| c.index == self.index)
+            .ok_or(VssError::InvalidSignatureShares(vec![self.index]))?;
+        let generator = Secp256k1Point::generator();
+        if nonces.index != self.index
+            || generator * nonces.hiding != own.hiding
+            || generator * nonces.binding != own.binding
+        {
+            return Err(VssError::InvalidSignatureShares(vec![self.index]));
+        }
+        let nonce = nonces.hiding + session.binding_factor(self.index) * nonces.binding;
+        let nonce = if session.negate_nonce { -nonce } else { nonce };
+        let share = if session.negate_key {
+            -self.share
+        } else {
+            self.share
+        };
+        Ok(SignatureShare {
+            index: self.index,
+            response: nonce + session.challenge * session.lagrange(self.index) * share,
+        })
+    }
+}
+
+/// Check a signature share against the signer's verification key from the commitments.
+pub fn verify_share(
+    package: &SigningPackage,
+    share: &SignatureShare,
+    commitments: &[Secp256k1Point],
+) -> Result<bool, VssError> {
+    Session::new(package, commitments)?.verify_share(share, commitments)
+}
+
+/// Check every signature share and combine them into a BIP-340 signature.
+///
+/// Fails with `VssError::InvalidSignatureShares` listing the signers whose
+/// shares are missing or do not verify.
+pub fn aggregate(
+    package: &SigningPackage,
+    shares: &[SignatureShare],
+    commitments: &[Secp256k1Point],
+) -> Result<SchnorrSignature, VssError> {
+    let session = Session::new(package, commitments)?;
+    let mut invalid = vec![];
+    let mut s = Secp256k1Scalar::zero();
+    for commitment in package.commitments.iter() {
+        match shares.iter().find(|share| share.index == commitment.index) {
+            Some(share) if session.verify_share(share, commitments)? => {
+                s = s + share.response;
+            }
+            _ => invalid.push(commitment.index),
+        }
+    }
+    if !invalid.is_empty() {
+        return Err(VssError::InvalidSignatureShares(invalid));
+    }
+    Ok(SchnorrSignature {
+        r: session.nonce.x_only(),
+        s,
+    })
+}
+
+/// Verify a BIP-340 signature under an x-only public key.
+pub fn verify_signature(public_key: &[u8; 32], message: &[u8], signature: &[u8; 64]) -> bool {
+    let public_key = match Secp256k1Point::from_x_only(public_key) {
+        Some(point) => point,
+        None => return false,
+    };
+    let s = match Secp256k1Scalar::from_slice(&signature[32..]) {
+        Some(s) => s,
+        None => return false,
+    };
+    let mut r = [0u8; 32];
+    r.copy_from_slice(&signature[..32]);
+    let challenge = bip340_challenge(&r, &public_key.x_only(), message);
+    let nonce = Secp256k1Point::generator() * s + (-public_key) * challenge;
+    nonce.has_even_y() && nonce.x_only() == r
+}
+
+/// The per-package values every signer and the aggregator derive identically.
+struct Session {
+    signers: Vec<usize>,
+    binding_factors: Vec<Secp256k1Scalar>,
+    signer_nonces: Vec<Secp256k1Point>,
+    lagrange_coefficients: Vec<Secp256k1Scalar>,
+    nonce: Secp256k1Point,
+    challenge: Secp256k1Scalar,
+    negate_nonce: bool,
+    negate_key: bool,
+}
+
+impl Session {
+    fn new(package: &SigningPackage, commitments: &[Secp256k1Point]) -> Result<Session, VssError> {
+        let group_key = *commitments.first().ok_or(VssError::EmptyCommitments)?;
+        if package.commitments.len() < commitments.len() {
+            return Err(VssError::TooFewShares {
+                required: commitments.len(),
+                provided: package.commitments.len(),
+            });
+        }
+        let indexed: Vec<(usize, ())> = package.commitments.iter().map(|c| (c.index, ())).collect();
+        check_share_indices(&indexed)?;
+        let signers: Vec<usize> = package.commitments.iter().map(|c| c.index).collect();
+
+        // the binding factors cover the group key, as in RFC 9591
+        let mut transcript = Transcript::new(b"verifiable_secret_sharing/frost/binding");
+        transcript.append_point(b"group key", &group_key);
+        transcript.append_message(b"message", &package.message);
+        for commitment in package.commitments.iter() {
+            transcript.append_u64(b"index", commitment.index as u64);
+            transcript.append_point(b"hiding", &commitment.hiding);
+            transcript.append_point(b"binding", &commitment.binding);
+        }
+        let binding_factors: Vec<Secp256k1Scalar> = signers
+            .iter()
+            .map(|index| {
+                let mut transcript = transcript.clone();
+                transcript.append_u64(b"signer", *index as u64);
+                transcript.challenge_scalar(b"binding factor")
+            })
+            .collect();
+
+        let signer_nonces: Vec<Secp256k1Point> = package
+            .commitments
+            .iter()
+            .zip(binding_factors.iter())
+            .map(|(c, rho)| c.hiding + c.binding * *rho)
+            .collect();
+        // the group nonce sums hiding + rho * binding over all signers at once
+        let (scalars, points): (Vec<Secp256k1Scalar>, Vec<Secp256k1Point>) = package
+            .commitments
+            .iter()
+            .zip(binding_factors.iter())
+            .flat_map(|(c, rho)| vec![(Secp256k1Scalar::one(), c.hiding), (*rho, c.binding)])
+            .unzip();
+        let nonce = Secp256k1Point::multiscalar_mul(&scalars, &points);
+        let negate_nonce = !nonce.has_even_y();
+        let nonce = if negate_nonce { -nonce } else { nonce };
+        let negate_key = !group_key.has_even_y();
+        let x_only_key = if negate_key { -group_key } else { group_key }.x_only();
+        let challenge = bip340_challenge(&nonce.x_only(), &x_only_key, &package.message);
+        Ok(Session {
+            lagrange_coefficients: VerifiableSecretSharing::<Secp256k1>::lagrange_coefficients(
+                Secp256k1Scalar::zero(),
+                &signers,
+            ),
+            signers,
+            binding_factors,
+            signer_nonces,
+            nonce,
+            challenge,
+            negate_nonce,
+            negate_key,
+        })
+    }
+
+    fn position(&self, index: usize) -> usize {
+        self.signers.iter().position(|i| *i == index).unwrap()
+    }
+
+    fn binding_factor(&self, index: usize) -> Secp256k1Scalar {
+        self.binding_factors[self.position(index)]
+    }
+
+    fn lagrange(&self, index: usize) -> Secp256k1Scalar {
+        self.lagrange_coefficients[self.position(index)]
+    }
+
+    fn verify_share(
+        &self,
+        share: &SignatureShare,
+        commitments: &[Secp256k1Point],
+    ) -> Result<bool, VssError> {
+        let position = match self.signers.iter().position(|i| *i == share.index) {
+            Some(position) => position,
+            None => return Ok(false),
+        };
+        let nonce = self.signer_nonces[position];
+        let nonce = if self.negate_nonce { -nonce } else { nonce };
+        let verification_key =
+            VerifiableSecretSharing::evaluate_commitments(commitments, share.index)?;
+        let verification_key = if self.negate_key {
+            -verification_key
+        } else {
+            verification_key
+        };
+        let expected =
+            nonce + verification_key * (self.challenge * self.lagrange_coefficients[position]);
+        Ok(Secp256k1Point::generator() * share.response == expected)
+    }
+}
+
+fn bip340_challenge(r: &[u8; 32], public_key: &[u8; 32], message: &[u8]) -> Secp256k1Scalar {
+    let tag = Sha256::digest(b"BIP0340/challenge");
+    let mut hasher = Sha256::new();
+    hasher.update(tag);
+    hasher.update(tag);
+    hasher.update(r);
+    hasher.update(public_key);
+    hasher.update(message);
+    Secp256k1Scalar::from_bytes_mod_order(&hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curve::tests::deal;
+    use crate::curve::Secp256k1;
+    use k256::schnorr::{Signature, VerifyingKey};
+    use std::convert::TryFrom;
+
+    fn sign(signers: &[FrostSigner], message: &[u8]) -> (SigningPackage, Vec<SignatureShare>) {
+        let (nonces, commitments): (Vec<SigningNonces>, Vec<NonceCommitment>) =
+            signers.iter().map(|signer| signer.commit()).unzip();
+        let package = SigningPackage {
+            message: message.to_vec(),
+            commitments,
+        };
+        let shares = signers
+            .iter()
+            .zip(nonces)
+            .map(|(signer, nonces)| signer.sign(&package, nonces).unwrap())
+            .collect();
+        (package, shares)
+    }
+
+    fn new_signers(
+        shares: &[(usize, Secp256k1Scalar)],
+        commitments: &[Secp256k1Point],
+    ) -> Vec<FrostSigner> {
+        shares
+            .iter()
+            .map(|share| FrostSigner::new(*share, commitments).unwrap())
+            .collect()
+    }
+
+    fn x_only_group_key(commitments: &[Secp256k1Point]) -> [u8; 32] {
+        commitments[0].x_only()
+    }
+
+    #[test]
+    fn test_frost_3_of_5_is_bip340_valid() {
+        for _ in 0..4 {
+            let (_, shares, commitments) = deal(Secp256k1, 3, 5);
+            let signers = new_signers(&shares, &commitments);
+            let chosen: Vec<FrostSigner> =
+                signers.into_iter().filter(|s| s.index % 2 == 1).collect();
+            let message = [0xab; 32];
+            let (package, shares) = sign(&chosen, &message);
+            for share in shares.iter() {
+                assert!(verify_share(&package, share, &commitments).unwrap());
+            }
+            let signature = aggregate(&package, &shares, &commitments).unwrap();
+            let public_key = x_only_group_key(&commitments);
+            assert!(verify_signature(
+                &public_key,
+                &message,
+                &signature.serialize()
+            ));
+
+            let verifying_key = VerifyingKey::from_bytes(&public_key).unwrap();
+            let k256_signature = Signature::try_from(&signature.serialize()[..]).unwrap();
+            assert!(verifying_key.verify_raw(&message, &k256_signature).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_frost_identifies_bad_share() {
+        let (_, shares, commitments) = deal(Secp256k1, 2, 3);
+        let signers = new_signers(&shares, &commitments);
+        let (package, mut shares) = sign(&signers[1..], b"message");
+        shares[1].response = shares[1].response + Secp256k1Scalar::one();
+        assert!(!verify_share(&package, &shares[1], &commitments).unwrap());
+        assert_eq!(
+            aggregate(&package, &shares, &commitments).unwrap_err(),
+            VssError::InvalidSignatureShares(vec![3])
+        );
+    }
+
+    #[test]
+    fn test_frost_signer_rejects_invalid_key_share() {
+        let (_, shares, commitments) = deal(Secp256k1, 2, 3);
+        let forged = (shares[1].0, shares[1].1 + Secp256k1Scalar::one());
+        assert_eq!(
+            FrostSigner::new(forged, &commitments).err(),
+            Some(VssError::InvalidShares(vec![2]))
+        );
+    }
+
+    #[test]
+    fn test_frost_rejects_too_few_signers() {
+        let (_, shares, commitments) = deal(Secp256k1, 3, 4);
+        let signers = new_signers(&shares, &commitments);
+        let (nonces, commitment) = signers[0].commit();
+        let package = SigningPackage {
+            message: b"message".to_vec(),
+            commitments: vec![commitment],
+        };
+        assert_eq!(
+            signers[0].sign(&package, nonces).unwrap_err(),
+            VssError::TooFewShares {
+                required: 3,
+                provided: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_verify_signature_rejects_tampering() {
+        let (_, shares, commitments) = deal(Secp256k1, 2, 2);
+        let signers = new_signers(&shares, &commitments);
+        let (package, shares) = sign(&signers, b"message");
+        let signature = aggregate(&package, &shares, &commitments)
+            .unwrap()
+            .serialize();
+        let public_key = x_only_group_key(&commitments);
+        assert!(verify_signature(&public_key, b"message", &signature));
+        assert!(!verify_signature(&public_key, b"massage", &signature));
+    }
+}
@@ -0,0 +1,265 @@
+use crate::errors::{check_share_indices, VssError};
+use rand::{thread_rng, Rng};
+
+/// The `Gf256SecretSharing` splits byte strings of any length with Shamir's
+/// scheme over GF(2^8), byte by byte, as SLIP-39 and HashiCorp Vault do.
+///
+/// Every share is as long as the secret and carries a 1 byte x coordinate,
+/// so at most 255 shares can be generated. The field is the AES field
+/// GF(2)[x]/(x^8 + x^4 + x^3 + x + 1), and its arithmetic is branch-free
+/// and table-free so that it runs in constant time.
+#[derive(Clone, Debug)]
+pub struct Gf256SecretSharing {
+    /// the threshold of shares to recover the secret.
+    pub threshold: usize,
+    /// the total number of shares to generate from the secret.
+    pub share_amount: usize,
+}
+
+impl Gf256SecretSharing {
+    /// Split a secret according to the config.
+    ///
+    /// Panics if the threshold or share amount is invalid, see `try_split`.
+    pub fn split(&self, secret: &[u8]) -> Vec<(u8, Vec<u8>)> {
+        self.try_split(secret).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Split a secret according to the config, failing on an invalid
+    /// threshold or more than 255 shares.
+    pub fn try_split(&self, secret: &[u8]) -> Result<Vec<(u8, Vec<u8>)>, VssError> {
+        self.try_split_with_rng(secret, &mut thread_rng())
+    }
+
+    /// Split a secret, sampling the polynomials from `rng`.
+    pub fn try_split_with_rng<R: Rng + ?Sized>(
+        &self,
+        secret: &[u8],
+        rng: &mut R,
+    ) -> Result<Vec<(u8, Vec<u8>)>, VssError> {
+        if self.threshold == 0 || self.threshold > self.share_amount {
+            return Err(VssError::InvalidThreshold {
+                threshold: self.threshold,
+                share_amount: self.share_amount,
+            });
+        }
+        if self.share_amount > 255 {
+            return Err(VssError::TooManyShares {
+                maximum: 255,
+                requested: self.share_amount,
+            });
+        }
+        // one polynomial per secret byte, its constant term being that byte
+        let polynomials: Vec<Vec<u8>> = secret
+            .iter()
+            .map(|byte| {
+                let mut coefficients = vec![0u8; self.threshold];
+                coefficients[0] = *byte;
+                rng.fill(&mut coefficients[1..]);
+                coefficients
+            })
+            .collect();
+        Ok((1..=self.share_amount as u8)
+            .map(|x| {
+                let ys = polynomials
+                    .iter()
+                    .map(|polynomial| evaluate_at(polynomial, x))
+                    .collect();
+                (x, ys)
+            })
+            .collect())
+    }
+
+    /// Recover the secret by at least threshold shares.
+    ///
+    /// Panics if the shares are invalid or inconsistent, see `try_recover`.
+    pub fn recover(&self, shares: &[(u8, Vec<u8>)]) -> Vec<u8> {
+        self.try_recover(shares)
+            .unwrap_or_else(|e| panic!("wrong shares: {}", e))
+    }
+
+    /// Recover the secret by at least threshold shares.
+    ///
+    /// The secret is interpolated from the first threshold shares, every extra
+    /// share is then checked against those polynomials and the indices of
+    /// those which disagree, or whose length differs from the first share,
+    /// are reported as `VssError::InconsistentShares`, which does not
+    /// necessarily name the corrupt shares.
+    pub fn try_recover(&self, shares: &[(u8, Vec<u8>)]) -> Result<Vec<u8>, VssError> {
+        if self.threshold == 0 || shares.len() < self.threshold {
+            return Err(VssError::TooFewShares {
+                required: self.threshold,
+                provided: shares.len(),
+            });
+        }
+        let indexed: Vec<(usize, ())> = shares.iter().map(|(x, _)| (*x as usize, ())).collect();
+        check_share_indices(&indexed)?;
+        let length = shares[0].1.len();
+        let wrong_length: Vec<usize> = shares
+            .iter()
+            .filter(|(_, ys)| ys.len() != length)
+            .map(|(x, _)| *x as usize)
+            .collect();
+        if !wrong_length.is_empty() {
+            return Err(VssError::InconsistentShares(wrong_length));
+        }
+
+        let (xs, ys): (Vec<u8>, Vec<&Vec<u8>>) = shares[..self.threshold]
+            .iter()
+            .map(|(x, ys)| (*x, ys))
+            .unzip();
+        let inconsistent: Vec<usize> = shares[self.threshold..]
+            .iter()
+            .filter(|(x, y)| interpolate(*x, &xs, &ys) != *y)
+            .map(|(x, _)| *x as usize)
+            .collect();
+        if !inconsistent.is_empty() {
+            return Err(VssError::InconsistentShares(inconsistent));
+        }
+        Ok(interpolate(0, &xs, &ys))
+    }
+}
+
+/// Evaluate the polynomial at `x` by Horner's rule.
+fn evaluate_at(polynomial: &[u8], x: u8) -> u8 {
+    polynomial
+        .iter()
+        .rev()
+        .fold(0, |sum, coefficient| mul(sum, x) ^ coefficient)
+}
+
+/// Evaluate at `x`, byte by byte, the polynomials through `(xs[i], ys[i])`.
+pub(crate) fn interpolate(x: u8, xs: &[u8], ys: &[&Vec<u8>]) -> Vec<u8> {
+    let coefficients: Vec<u8> = (0..xs.len())
+        .map(|item| {
+            let (numerator, denominator) =
+                xs.iter()
+                    .enumerate()
+                    .fold((1, 1), |(numerator, denominator), (i, x_i)| {
+                        if i == item {
+                            (numerator, denominator)
+                        } else {
+                            // subtraction is addition in characteristic 2
+                            (mul(numerator, x ^ x_i), mul(denominator, xs[item] ^ x_i))
+                        }
+                    });
+            mul(numerator, inverse(denominator))
+        })
+        .collect();
+    (0..ys[0].len())
+        .map(|k| {
+            coefficients
+                .iter()
+                .zip(ys.iter())
+                .fold(0, |sum, (coefficient, y)| sum ^ mul(*coefficient, y[k]))
+        })
+        .collect()
+}
+
+/// Multiply in GF(2^8) by shift-and-add, masking instead of branching on bits.
+fn mul(a: u8, b: u8) -> u8 {
+    let (mut a, mut b, mut product) = (a, b, 0u8);
+    for _ in 0..8 {
+        product ^= a & (b & 1).wrapping_neg();
+        let carry = (a >> 7).wrapping_neg();
+        a = (a << 1) ^ (0x1b & carry);
+        b >>= 1;
+    }
+    product
+}
+
+/// Invert in GF(2^8) as `a^254`, with a fixed chain of squarings and
+/// multiplications. Zero maps to zero.
+fn inverse(a: u8) -> u8 {
+    // a^254 = a^(2 + 4 + 8 + 16 + 32 + 64 + 128)
+    let mut square = mul(a, a);
+    let mut result = square;
+    for _ in 0..6 {
+        square = mul(square, square);
+        result = mul(result, square);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gf256_arithmetic() {
+        // the worked example of FIPS-197 section 4.2
+        assert_eq!(mul(0x57, 0x83), 0xc1);
+        assert_eq!(mul(0x57, 0x13), 0xfe);
+        assert_eq!(inverse(0), 0);
+        for a in 1..=255u8 {
+            assert_eq!(mul(a, inverse(a)), 1);
+        }
+    }
+
+    #[test]
+    fn test_gf256_sss_3_of_5_works() {
+        let sss = Gf256SecretSharing {
+            threshold: 3,
+            share_amount: 5,
+        };
+        let secret = b"correct horse battery staple".to_vec();
+        let shares = sss.split(&secret);
+        assert_eq!(shares.len(), 5);
+        for (_, ys) in shares.iter() {
+            assert_eq!(ys.len(), secret.len());
+        }
+        assert_eq!(sss.recover(&shares[..3]), secret);
+        assert_eq!(sss.recover(&shares[2..]), secret);
+        assert_eq!(sss.recover(&shares), secret);
+        assert_eq!(sss.recover(&sss.split(b"")[1..4]), Vec::<u8>::new());
+    }
+
+    #[test]
+    fn test_gf256_sss_large_secret_and_share_amount() {
+        let mut secret = vec![0u8; 4096];
+        thread_rng().fill(&mut secret[..]);
+        let sss = Gf256SecretSharing {
+            threshold: 2,
+            share_amount: 255,
+        };
+        let shares = sss.split(&secret);
+        assert_eq!(sss.recover(&shares[253..]), secret);
+
+        let too_many = Gf256SecretSharing {
+            threshold: 2,
+            share_amount: 256,
+        };
+        assert_eq!(
+            too_many.try_split(&secret).unwrap_err(),
+            VssError::TooManyShares {
+                maximum: 255,
+                requested: 256
+            }
+        );
+    }
+
+    #[test]
+    fn test_gf256_sss_reports_inconsistent_shares() {
+        let sss = Gf256SecretSharing {
+            threshold: 2,
+            share_amount: 4,
+        };
+        let mut shares = sss.split(b"secret");
+        shares[3].1[0] ^= 1;
+        assert_eq!(
+            sss.try_recover(&shares).unwrap_err(),
+            VssError::InconsistentShares(vec![4])
+        );
+        shares[2].1.pop();
+        assert_eq!(
+            sss.try_recover(&shares[..3]).unwrap_err(),
+            VssError::InconsistentShares(vec![3])
+        );
+        assert_eq!(
+            sss.try_recover(&shares[..1]).unwrap_err(),
+            VssError::TooFewShares {
+                required: 2,
+                provided: 1
+            }
+        );
+    }
+}
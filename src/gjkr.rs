@@ -0,0 +1,732 @@
+use crate::curve::Secp256k1;
+use crate::dkg::{survives_complaints, Complaint, DealerBroadcast, DkgOutput};
+use crate::errors::VssError;
+use crate::feldman_vss::VerifiableSecretSharing;
+use crate::field::{self, ScalarField};
+use crate::pedersen_vss::PedersenVss;
+use crate::secp256k1_helper::{Secp256k1Point, Secp256k1Scalar};
+use rand::rngs::StdRng;
+use rand::{thread_rng, Rng, SeedableRng};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// The rounds of the GJKR key generation, in order.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum GjkrPhase {
+    /// dealing Pedersen shares and complaining about bad ones.
+    Sharing,
+    /// the qualified set is fixed; dealers reveal Feldman commitments.
+    Extracting,
+    /// secrets of dealers with bad Feldman commitments are reconstructed.
+    Reconstructing,
+    /// the output has been computed.
+    Finished,
+}
+
+/// The Pedersen commitments every dealer broadcasts in the first round.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct PedersenBroadcast {
+    /// the index of the dealer.
+    pub dealer: usize,
+    /// the Pedersen commitments `g^a_k * h^b_k`.
+    pub commitments: Vec<Secp256k1Point>,
+}
+
+/// A dealer's public answer to a sharing complaint, revealing the disputed share pair.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct PedersenComplaintAnswer {
+    /// the index of the dealer answering.
+    pub dealer: usize,
+    /// the index of the participant who complained.
+    pub accuser: usize,
+    /// the disputed share `f_dealer(accuser)`.
+    pub share: Secp256k1Scalar,
+    /// the disputed blinding share `f'_dealer(accuser)`.
+    pub blinding_share: Secp256k1Scalar,
+}
+
+/// A complaint that a qualified dealer's Feldman commitments do not match its share.
+///
+/// The share pair is revealed so everyone can check it against the dealer's
+/// Pedersen commitments.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct ExtractionComplaint {
+    /// the index of the participant complaining.
+    pub accuser: usize,
+    /// the index of the dealer complained against.
+    pub dealer: usize,
+    /// the share received from the dealer.
+    pub share: Secp256k1Scalar,
+    /// the blinding share received from the dealer.
+    pub blinding_share: Secp256k1Scalar,
+}
+
+/// A share of a cheating dealer revealed so that its polynomial can be reconstructed.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct RevealedShare {
+    /// the index of the participant revealing.
+    pub holder: usize,
+    /// the index of the dealer whose share is revealed.
+    pub dealer: usize,
+    /// the share received from the dealer.
+    pub share: Secp256k1Scalar,
+    /// the blinding share received from the dealer.
+    pub blinding_share: Secp256k1Scalar,
+}
+
+/// A participant in the Gennaro-Jarecki-Krawczyk-Rabin secure distributed key generation.
+///
+/// The qualified set is fixed while every commitment is still hiding (Pedersen
+/// VSS), and only then are Feldman commitments revealed, so a rushing
+/// adversary cannot bias the group key. A qualified dealer whose Feldman
+/// commitments are wrong has its polynomial reconstructed from revealed shares.
+pub struct GjkrParticipant {
+    /// the index of the participant, from `1` to `share_amount`.
+    pub index: usize,
+    phase: GjkrPhase,
+    pedersen: PedersenVss,
+    shares: Vec<(usize, Secp256k1Scalar, Secp256k1Scalar)>,
+    pedersen_commitments: Vec<Secp256k1Point>,
+    feldman_commitments: Vec<Secp256k1Point>,
+    pedersen_broadcasts: Vec<PedersenBroadcast>,
+    received: BTreeMap<usize, (Secp256k1Scalar, Secp256k1Scalar)>,
+    qualified: Vec<usize>,
+    feldman_broadcasts: BTreeMap<usize, Vec<Secp256k1Point>>,
+    reconstructed: Vec<usize>,
+}
+
+impl GjkrParticipant {
+    /// Create a participant and deal its random secret.
+    pub fn new(
+        index: usize,
+        threshold: usize,
+        share_amount: usize,
+    ) -> Result<GjkrParticipant, VssError> {
+        Self::new_with_rng(index, threshold, share_amount, &mut thread_rng())
+    }
+
+    /// Create a participant and deal its random secret, sampled from `rng`.
+    ///
+    /// Fails on an index outside `1..=share_amount`.
+    pub fn new_with_rng<R: Rng + ?Sized>(
+        index: usize,
+        threshold: usize,
+        share_amount: usize,
+        rng: &mut R,
+    ) -> Result<GjkrParticipant, VssError> {
+        if index == 0 {
+            return Err(VssError::ZeroShareIndex);
+        }
+        if index > share_amount {
+            return Err(VssError::ShareIndexOutOfRange {
+                index,
+                share_amount,
+            });
+        }
+        let pedersen = PedersenVss {
+            threshold,
+            share_amount,
+            curve: Secp256k1,
+        };
+        let secret = Secp256k1Scalar::random(rng);
+        let ((shares, pedersen_commitments), polynomial) = pedersen.deal_with_rng(&secret, rng)?;
+        Ok(GjkrParticipant {
+            index,
+            phase: GjkrPhase::Sharing,
+            pedersen,
+            shares,
+            pedersen_commitments,
+            feldman_commitments: VerifiableSecretSharing::generate_commitments(&polynomial),
+            pedersen_broadcasts: vec![],
+            received: BTreeMap::new(),
+            qualified: vec![],
+            feldman_broadcasts: BTreeMap::new(),
+            reconstructed: vec![],
+        })
+    }
+
+    /// The current round.
+    pub fn phase(&self) -> GjkrPhase {
+        self.phase
+    }
+
+    /// Round 1: the Pedersen commitments to broadcast to every participant.
+    pub fn pedersen_broadcast(&self) -> PedersenBroadcast {
+        PedersenBroadcast {
+            dealer: self.index,
+            commitments: self.pedersen_commitments.clone(),
+        }
+    }
+
+    /// Round 1: the share pair to send privately to `recipient`, or `None`
+    /// for an index outside `1..=share_amount`.
+    pub fn share_for(&self, recipient: usize) -> Option<(Secp256k1Scalar, Secp256k1Scalar)> {
+        let position = recipient.checked_sub(1)?;
+        let (_, share, blinding_share) = *self.shares.get(position)?;
+        Some((share, blinding_share))
+    }
+
+    /// Round 1: receive a dealer's broadcast and private share pair,
+    /// complaining if the pair does not match the Pedersen commitments.
+    pub fn receive_share(
+        &mut self,
+        broadcast: &PedersenBroadcast,
+        share: (Secp256k1Scalar, Secp256k1Scalar),
+    ) -> Result<Option<Complaint>, VssError> {
+        self.expect_phase(GjkrPhase::Sharing)?;
+        self.pedersen_broadcasts.push(broadcast.clone());
+        let (value, blinding_value) = share;
+        let valid = broadcast.commitments.len() == self.pedersen.threshold
+            && PedersenVss::try_verify((self.index, value, blinding_value), &broadcast.commitments)
+                .unwrap_or(false);
+        if valid {
+            self.received.insert(broadcast.dealer, share);
+            Ok(None)
+        } else {
+            Ok(Some(Complaint {
+                accuser: self.index,
+                dealer: broadcast.dealer,
+            }))
+        }
+    }
+
+    /// Round 2: answer a complaint against this participant by revealing the disputed pair.
+    pub fn answer(&self, complaint: &Complaint) -> Option<PedersenComplaintAnswer> {
+        if complaint.dealer != self.index || complaint.accuser == 0 {
+            return None;
+        }
+        self.shares
+            .get(complaint.accuser - 1)
+            .map(|(_, share, blinding_share)| PedersenComplaintAnswer {
+                dealer: self.index,
+                accuser: complaint.accuser,
+                share: *share,
+                blinding_share: *blinding_share,
+            })
+    }
+
+    /// Round 2: fix the qualified set from the public complaints and answers,
+    /// adopting the answers to this participant's own complaints.
+    pub fn qualify(
+        &mut self,
+        complaints: &[Complaint],
+        answers: &[PedersenComplaintAnswer],
+    ) -> Result<Vec<usize>, VssError> {
+        self.expect_phase(GjkrPhase::Sharing)?;
+        let index = self.index;
+        for answer in answers.iter().filter(|a| a.accuser == index) {
+            let commitments = self
+                .pedersen_broadcasts
+                .iter()
+                .find(|b| b.dealer == answer.dealer)
+                .map(|b| &b.commitments);
+            if let Some(commitments) = commitments {
+                if PedersenVss::try_verify(
+                    (index, answer.share, answer.blinding_share),
+                    commitments,
+                )
+                .unwrap_or(false)
+                {
+                    self.received
+                        .insert(answer.dealer, (answer.share, answer.blinding_share));
+                }
+            }
+        }
+        self.qualified = gjkr_qualified_dealers(
+            self.pedersen.threshold,
+            self.pedersen.share_amount,
+            &self.pedersen_broadcasts,
+            complaints,
+            answers,
+        );
+        self.phase = GjkrPhase::Extracting;
+        Ok(self.qualified.clone())
+    }
+
+    /// Round 3: the Feldman commitments to broadcast, revealed only after qualification.
+    pub fn feldman_broadcast(&self) -> Result<DealerBroadcast, VssError> {
+        self.expect_phase(GjkrPhase::Extracting)?;
+        Ok(DealerBroadcast {
+            dealer: self.index,
+            commitments: self.feldman_commitments.clone(),
+        })
+    }
+
+    /// Round 3: receive a qualified dealer's Feldman commitments,
+    /// complaining if they do not match the share received from it.
+    pub fn receive_feldman(
+        &mut self,
+        broadcast: &DealerBroadcast,
+    ) -> Result<Option<ExtractionComplaint>, VssError> {
+        self.expect_phase(GjkrPhase::Extracting)?;
+        if !self.qualified.contains(&broadcast.dealer) {
+            return Ok(None);
+        }
+        self.feldman_broadcasts
+            .insert(broadcast.dealer, broadcast.commitments.clone());
+        let (share, blinding_share) = *self
+            .received
+            .get(&broadcast.dealer)
+            .ok_or(VssError::MissingDealerShare(broadcast.dealer))?;
+        let valid = broadcast.commitments.len() == self.pedersen.threshold
+            && VerifiableSecretSharing::try_verify((self.index, share), &broadcast.commitments)
+                .unwrap_or(false);
+        if valid {
+            Ok(None)
+        } else {
+            Ok(Some(ExtractionComplaint {
+                accuser: self.index,
+                dealer: broadcast.dealer,
+                share,
+                blinding_share,
+            }))
+        }
+    }
+
+    /// Round 4: reveal this participant's shares of every qualified dealer
+    /// whose Feldman commitments are missing, malformed or validly complained about.
+    pub fn reveal(
+        &mut self,
+        complaints: &[ExtractionComplaint],
+    ) -> Result<Vec<RevealedShare>, VssError> {
+        self.expect_phase(GjkrPhase::Extracting)?;
+        let threshold = self.pedersen.threshold;
+        self.reconstructed = self
+            .qualified
+            .iter()
+            .cloned()
+            .filter(|dealer| match self.feldman_broadcasts.get(dealer) {
+                None => true,
+                Some(feldman) => {
+                    feldman.len() != threshold
+                        || complaints.iter().any(|complaint| {
+                            complaint.dealer == *dealer
+                                && self.is_valid_extraction_complaint(complaint, feldman)
+                        })
+                }
+            })
+            .collect();
+        self.phase = GjkrPhase::Reconstructing;
+        self.reconstructed
+            .iter()
+            .map(|dealer| {
+                let (share, blinding_share) = *self
+                    .received
+                    .get(dealer)
+                    .ok_or(VssError::MissingDealerShare(*dealer))?;
+                Ok(RevealedShare {
+                    holder: self.index,
+                    dealer: *dealer,
+                    share,
+                    blinding_share,
+                })
+            })
+            .collect()
+    }
+
+    /// Round 5: reconstruct the cheating dealers' polynomials and compute the output.
+    pub fn finalize(&mut self, revealed: &[RevealedShare]) -> Result<DkgOutput, VssError> {
+        self.expect_phase(GjkrPhase::Reconstructing)?;
+        let threshold = self.pedersen.threshold;
+        let mut share = Secp256k1Scalar::zero();
+        let mut commitments: Option<Vec<Secp256k1Point>> = None;
+        for dealer in self.qualified.iter() {
+            let (received, _) = self
+                .received
+                .get(dealer)
+                .ok_or(VssError::MissingDealerShare(*dealer))?;
+            share = share + *received;
+            let dealer_commitments = if self.reconstructed.contains(dealer) {
+                let pedersen_commitments = self.pedersen_commitments_of(*dealer)?;
+                // one point per holder, a repeated x would break the interpolation
+                let mut holders = BTreeSet::new();
+                let points: Vec<(usize, Secp256k1Scalar)> = revealed
+                    .iter()
+                    .filter(|r| {
+                        r.dealer == *dealer
+                            && PedersenVss::try_verify(
+                                (r.holder, r.share, r.blinding_share),
+                                pedersen_commitments,
+                            )
+                            .unwrap_or(false)
+                            && holders.insert(r.holder)
+                    })
+                    .map(|r| (r.holder, r.share))
+                    .take(threshold)
+                    .collect();
+                if points.len() < threshold {
+                    return Err(VssError::TooFewShares {
+                        required: threshold,
+                        provided: points.len(),
+                    });
+                }
+                let (xs, ys): (Vec<usize>, Vec<Secp256k1Scalar>) = points.into_iter().unzip();
+                VerifiableSecretSharing::generate_commitments(&field::interpolate_polynomial(
+                    &ScalarField::new(),
+                    &xs,
+                    &ys,
+                ))
+            } else {
+                self.feldman_broadcasts[dealer].clone()
+            };
+            commitments = Some(match commitments {
+                None => dealer_commitments,
+                Some(sum) => sum
+                    .iter()
+                    .zip(dealer_commitments.iter())
+                    .map(|(a, b)| *a + *b)
+                    .collect(),
+            });
+        }
+        let commitments = commitments.ok_or(VssError::TooFewShares {
+            required: 1,
+            provided: 0,
+        })?;
+        self.phase = GjkrPhase::Finished;
+        Ok(DkgOutput {
+            index: self.index,
+            share,
+            group_public_key: commitments[0],
+            commitments,
+            qualified: self.qualified.clone(),
+        })
+    }
+
+    fn is_valid_extraction_complaint(
+        &self,
+        complaint: &ExtractionComplaint,
+        feldman: &[Secp256k1Point],
+    ) -> bool {
+        match self.pedersen_commitments_of(complaint.dealer) {
+            Ok(pedersen) => {
+                PedersenVss::try_verify(
+                    (complaint.accuser, complaint.share, complaint.blinding_share),
+                    pedersen,
+                )
+                .unwrap_or(false)
+                    && !VerifiableSecretSharing::try_verify(
+                        (complaint.accuser, complaint.share),
+                        feldman,
+                    )
+                    .unwrap_or(false)
+            }
+            Err(_) => false,
+        }
+    }
+
+    fn pedersen_commitments_of(&self, dealer: usize) -> Result<&[Secp256k1Point], VssError> {
+        self.pedersen_broadcasts
+            .iter()
+            .find(|b| b.dealer == dealer)
+            .map(|b| &b.commitments[..])
+            .ok_or(VssError::MissingDealerShare(dealer))
+    }
+
+    fn expect_phase(&self, expected: GjkrPhase) -> Result<(), VssError> {
+        if self.phase == expected {
+            Ok(())
+        } else {
+            Err(VssError::UnexpectedRound)
+        }
+    }
+}
+
+/// Compute the qualified dealers from the public transcript of the sharing phase.
+///
+/// A dealer is disqualified if its commitments have the wrong degree, if
+/// `threshold` or more participants complained about it, or if it failed to
+/// answer a complaint with a share pair that verifies. Only complaints from
+/// accusers in `1..=share_amount` count, each accuser once.
+pub fn gjkr_qualified_dealers(
+    threshold: usize,
+    share_amount: usize,
+    broadcasts: &[PedersenBroadcast],
+    complaints: &[Complaint],
+    answers: &[PedersenComplaintAnswer],
+) -> Vec<usize> {
+    let mut qualified: Vec<usize> = broadcasts
+        .iter()
+        .filter(|broadcast| broadcast.commitments.len() == threshold)
+        .filter(|broadcast| {
+            survives_complaints(
+                threshold,
+                share_amount,
+                broadcast.dealer,
+                complaints,
+                |accuser| {
+                    answers.iter().any(|answer| {
+                        answer.dealer == broadcast.dealer
+                            && answer.accuser == accuser
+                            && PedersenVss::try_verify(
+                                (answer.accuser, answer.share, answer.blinding_share),
+                                &broadcast.commitments,
+                            )
+                            .unwrap_or(false)
+                    })
+                },
+            )
+        })
+        .map(|broadcast| broadcast.dealer)
+        .collect();
+    qualified.sort_unstable();
+    qualified.dedup();
+    qualified
+}
+
+/// A deterministic in-memory run of the GJKR key generation among `share_amount` parties.
+///
+/// Sharing cheaters send a corrupted share pair to every other party and never
+/// answer complaints, so they are disqualified. Extraction cheaters behave
+/// during sharing but shift the constant term of their Feldman commitments to
+/// bias the group key; their polynomials are reconstructed instead.
+pub struct GjkrSimulation {
+    /// the threshold of shares to recover the group secret.
+    pub threshold: usize,
+    /// the number of parties.
+    pub share_amount: usize,
+    /// the parties cheating in the sharing phase.
+    pub sharing_cheaters: Vec<usize>,
+    /// the parties cheating in the extraction phase.
+    pub extraction_cheaters: Vec<usize>,
+}
+
+impl GjkrSimulation {
+    /// Run every round with randomness derived from `seed` and return the honest parties' outputs.
+    pub fn run(&self, seed: u64) -> Result<Vec<DkgOutput>, VssError> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut participants = (1..=self.share_amount)
+            .map(|index| {
+                GjkrParticipant::new_with_rng(index, self.threshold, self.share_amount, &mut rng)
+            })
+            .collect::<Result<Vec<GjkrParticipant>, VssError>>()?;
+
+        let broadcasts: Vec<PedersenBroadcast> = participants
+            .iter()
+            .map(|p| p.pedersen_broadcast())
+            .collect();
+        let mut complaints = vec![];
+        for dealer in 0..self.share_amount {
+            for recipient in 0..self.share_amount {
+                let (mut share, blinding_share) = participants[dealer]
+                    .share_for(recipient + 1)
+                    .expect("every recipient is a participant");
+                if self.sharing_cheaters.contains(&(dealer + 1)) && dealer != recipient {
+                    share = share + Secp256k1Scalar::one();
+                }
+                if let Some(complaint) = participants[recipient]
+                    .receive_share(&broadcasts[dealer], (share, blinding_share))?
+                {
+                    complaints.push(complaint);
+                }
+            }
+        }
+
+        let answers: Vec<PedersenComplaintAnswer> = complaints
+            .iter()
+            .filter(|complaint| !self.sharing_cheaters.contains(&complaint.dealer))
+            .filter_map(|complaint| participants[complaint.dealer - 1].answer(complaint))
+            .collect();
+        let mut qualified = vec![];
+        for participant in participants.iter_mut() {
+            qualified = participant.qualify(&complaints, &answers)?;
+        }
+
+        let mut feldman_broadcasts = vec![];
+        for dealer in qualified.iter() {
+            let mut broadcast = participants[dealer - 1].feldman_broadcast()?;
+            if self.extraction_cheaters.contains(dealer) {
+                broadcast.commitments[0] = broadcast.commitments[0] + Secp256k1Point::generator();
+            }
+            feldman_broadcasts.push(broadcast);
+        }
+        let mut extraction_complaints = vec![];
+        for participant in participants.iter_mut() {
+            for broadcast in feldman_broadcasts.iter() {
+                if let Some(complaint) = participant.receive_feldman(broadcast)? {
+                    extraction_complaints.push(complaint);
+                }
+            }
+        }
+
+        let mut revealed = vec![];
+        for participant in participants.iter_mut() {
+            revealed.extend(participant.reveal(&extraction_complaints)?);
+        }
+        participants
+            .iter_mut()
+            .filter(|participant| {
+                !self.sharing_cheaters.contains(&participant.index)
+                    && !self.extraction_cheaters.contains(&participant.index)
+            })
+            .map(|participant| participant.finalize(&revealed))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check_outputs(outputs: &[DkgOutput], threshold: usize) -> Secp256k1Scalar {
+        let group_public_key = outputs[0].group_public_key;
+        for output in outputs {
+            assert_eq!(output.group_public_key, group_public_key);
+            assert!(VerifiableSecretSharing::verify(
+                (output.index, output.share),
+                &output.commitments
+            ));
+        }
+        let vss = VerifiableSecretSharing {
+            threshold,
+            share_amount: outputs.len(),
+            curve: Secp256k1,
+        };
+        let shares: Vec<(usize, Secp256k1Scalar)> = outputs
+            .iter()
+            .map(|output| (output.index, output.share))
+            .collect();
+        let group_secret = vss.recover(&shares[..threshold]);
+        assert_eq!(Secp256k1Point::generator() * group_secret, group_public_key);
+        group_secret
+    }
+
+    #[test]
+    fn test_gjkr_3_of_5_works() {
+        let simulation = GjkrSimulation {
+            threshold: 3,
+            share_amount: 5,
+            sharing_cheaters: vec![],
+            extraction_cheaters: vec![],
+        };
+        let outputs = simulation.run(5).unwrap();
+        assert_eq!(outputs.len(), 5);
+        assert_eq!(outputs[0].qualified, vec![1, 2, 3, 4, 5]);
+        check_outputs(&outputs, 3);
+    }
+
+    #[test]
+    fn test_gjkr_disqualifies_sharing_cheater() {
+        let simulation = GjkrSimulation {
+            threshold: 2,
+            share_amount: 4,
+            sharing_cheaters: vec![3],
+            extraction_cheaters: vec![],
+        };
+        let outputs = simulation.run(11).unwrap();
+        assert_eq!(outputs[0].qualified, vec![1, 2, 4]);
+        check_outputs(&outputs, 2);
+    }
+
+    #[test]
+    fn test_gjkr_extraction_cheater_cannot_bias_key() {
+        let honest = GjkrSimulation {
+            threshold: 3,
+            share_amount: 5,
+            sharing_cheaters: vec![],
+            extraction_cheaters: vec![],
+        };
+        let cheating = GjkrSimulation {
+            threshold: 3,
+            share_amount: 5,
+            sharing_cheaters: vec![],
+            extraction_cheaters: vec![4],
+        };
+        let honest_outputs = honest.run(9).unwrap();
+        let cheating_outputs = cheating.run(9).unwrap();
+        assert_eq!(cheating_outputs.len(), 4);
+        assert_eq!(cheating_outputs[0].qualified, vec![1, 2, 3, 4, 5]);
+        assert_eq!(
+            honest_outputs[0].group_public_key,
+            cheating_outputs[0].group_public_key
+        );
+        check_outputs(&cheating_outputs, 3);
+    }
+
+    #[test]
+    fn test_gjkr_finalize_ignores_repeated_revealed_shares() {
+        let mut rng = StdRng::seed_from_u64(13);
+        let mut participants: Vec<GjkrParticipant> = (1..=3)
+            .map(|index| GjkrParticipant::new_with_rng(index, 2, 3, &mut rng).unwrap())
+            .collect();
+        let broadcasts: Vec<PedersenBroadcast> = participants
+            .iter()
+            .map(|p| p.pedersen_broadcast())
+            .collect();
+        for dealer in 0..3 {
+            for recipient in 0..3 {
+                let share = participants[dealer]
+                    .share_for(recipient + 1)
+                    .expect("every recipient is a participant");
+                let complaint = participants[recipient]
+                    .receive_share(&broadcasts[dealer], share)
+                    .unwrap();
+                assert_eq!(complaint, None);
+            }
+        }
+        for participant in participants.iter_mut() {
+            participant.qualify(&[], &[]).unwrap();
+        }
+        // dealer 3 biases its Feldman commitments and gets reconstructed
+        let mut feldman_broadcasts: Vec<DealerBroadcast> = participants
+            .iter()
+            .map(|p| p.feldman_broadcast().unwrap())
+            .collect();
+        feldman_broadcasts[2].commitments[0] =
+            feldman_broadcasts[2].commitments[0] + Secp256k1Point::generator();
+        let mut extraction_complaints = vec![];
+        for participant in participants.iter_mut() {
+            for broadcast in feldman_broadcasts.iter() {
+                extraction_complaints.extend(participant.receive_feldman(broadcast).unwrap());
+            }
+        }
+        let mut revealed = vec![];
+        for participant in participants.iter_mut() {
+            revealed.extend(participant.reveal(&extraction_complaints).unwrap());
+        }
+        // holder 1's valid share of dealer 3 is delivered twice, first
+        let repeated = *revealed
+            .iter()
+            .find(|r| r.dealer == 3 && r.holder == 1)
+            .unwrap();
+        revealed.insert(0, repeated);
+        let outputs: Vec<DkgOutput> = participants[..2]
+            .iter_mut()
+            .map(|participant| participant.finalize(&revealed).unwrap())
+            .collect();
+        check_outputs(&outputs, 2);
+    }
+
+    #[test]
+    fn test_gjkr_rejects_out_of_order_rounds() {
+        let mut participant = GjkrParticipant::new(1, 2, 3).unwrap();
+        assert_eq!(
+            participant.feldman_broadcast().unwrap_err(),
+            VssError::UnexpectedRound
+        );
+        assert_eq!(
+            participant.finalize(&[]).unwrap_err(),
+            VssError::UnexpectedRound
+        );
+        participant.qualify(&[], &[]).unwrap();
+        assert_eq!(participant.phase(), GjkrPhase::Extracting);
+    }
+
+    #[test]
+    fn test_gjkr_rejects_out_of_range_indices() {
+        let mut rng = StdRng::seed_from_u64(14);
+        let dealer = GjkrParticipant::new_with_rng(1, 2, 3, &mut rng).unwrap();
+        assert_eq!(dealer.share_for(0), None);
+        assert_eq!(dealer.share_for(4), None);
+        assert!(dealer.share_for(3).is_some());
+        assert_eq!(
+            GjkrParticipant::new_with_rng(0, 2, 3, &mut rng).err(),
+            Some(VssError::ZeroShareIndex)
+        );
+        assert_eq!(
+            GjkrParticipant::new_with_rng(4, 2, 3, &mut rng).err(),
+            Some(VssError::ShareIndexOutOfRange {
+                index: 4,
+                share_amount: 3
+            })
+        );
+    }
+}
@@ -0,0 +1,196 @@
+use crate::chunked::{decode_chunks, encode_chunks};
+use crate::errors::{check_share_indices, VssError};
+use crate::gf256_sss::interpolate;
+
+/// Rabin's information dispersal over GF(2^8): any `threshold` of the
+/// `fragment_amount` fragments rebuild the data, each fragment being about
+/// `|data| / threshold` bytes. Fragments reveal the data, there is no secrecy.
+///
+/// The data is framed with its length and cut into rows of `threshold`
+/// bytes, each row is read as the values at `x = 1..=threshold` of a
+/// polynomial, and fragment `x` holds the value at `x` of every row. The
+/// first `threshold` fragments are thus the data itself, a systematic
+/// Reed-Solomon code.
+///
+/// The rows are not polynomials over a `PrimeField`: every prime above 255
+/// needs more than a byte per value, so fragments would outgrow
+/// `|data| / threshold`. GF(2^8) holds exactly one byte per element, and its
+/// interpolation is shared with `Gf256SecretSharing`.
+#[derive(Clone, Debug)]
+pub struct InformationDispersal {
+    /// the number of fragments needed to rebuild the data.
+    pub threshold: usize,
+    /// the total number of fragments to generate from the data.
+    pub fragment_amount: usize,
+}
+
+impl InformationDispersal {
+    /// Split the data into fragments.
+    ///
+    /// Panics if the threshold or fragment amount is invalid, see `try_disperse`.
+    pub fn disperse(&self, data: &[u8]) -> Vec<(u8, Vec<u8>)> {
+        self.try_disperse(data).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Split the data into fragments, failing on an invalid threshold or
+    /// more than 255 fragments.
+    pub fn try_disperse(&self, data: &[u8]) -> Result<Vec<(u8, Vec<u8>)>, VssError> {
+        if self.threshold == 0 || self.threshold > self.fragment_amount {
+            return Err(VssError::InvalidThreshold {
+                threshold: self.threshold,
+                share_amount: self.fragment_amount,
+            });
+        }
+        if self.fragment_amount > 255 {
+            return Err(VssError::TooManyShares {
+                maximum: 255,
+                requested: self.fragment_amount,
+            });
+        }
+        let rows = encode_chunks(data, self.threshold);
+        let columns: Vec<Vec<u8>> = (0..self.threshold)
+            .map(|j| rows.iter().map(|row| row[j]).collect())
+            .collect();
+        let xs: Vec<u8> = (1..=self.threshold as u8).collect();
+        let ys: Vec<&Vec<u8>> = columns.iter().collect();
+        Ok((1..=self.fragment_amount as u8)
+            .map(|x| {
+                let fragment = if (x as usize) <= self.threshold {
+                    columns[x as usize - 1].clone()
+                } else {
+                    interpolate(x, &xs, &ys)
+                };
+                (x, fragment)
+            })
+            .collect())
+    }
+
+    /// Rebuild the data from at least threshold fragments.
+    ///
+    /// Panics if the fragments are invalid or inconsistent, see `try_reconstruct`.
+    pub fn reconstruct(&self, fragments: &[(u8, Vec<u8>)]) -> Vec<u8> {
+        self.try_reconstruct(fragments)
+            .unwrap_or_else(|e| panic!("wrong fragments: {}", e))
+    }
+
+    /// Rebuild the data from at least threshold fragments.
+    ///
+    /// The rows are interpolated from the first threshold fragments, every
+    /// extra fragment is then checked against them and the indices of those
+    /// which disagree, or whose length differs, are reported as
+    /// `VssError::InconsistentShares`, which does not necessarily name the
+    /// corrupt fragments.
+    pub fn try_reconstruct(&self, fragments: &[(u8, Vec<u8>)]) -> Result<Vec<u8>, VssError> {
+        if self.threshold == 0 || fragments.len() < self.threshold {
+            return Err(VssError::TooFewShares {
+                required: self.threshold,
+                provided: fragments.len(),
+            });
+        }
+        let indexed: Vec<(usize, ())> = fragments.iter().map(|(x, _)| (*x as usize, ())).collect();
+        check_share_indices(&indexed)?;
+        let length = fragments[0].1.len();
+        let wrong_length: Vec<usize> = fragments
+            .iter()
+            .filter(|(_, ys)| ys.len() != length)
+            .map(|(x, _)| *x as usize)
+            .collect();
+        if !wrong_length.is_empty() {
+            return Err(VssError::InconsistentShares(wrong_length));
+        }
+
+        let (xs, ys): (Vec<u8>, Vec<&Vec<u8>>) = fragments[..self.threshold]
+            .iter()
+            .map(|(x, ys)| (*x, ys))
+            .unzip();
+        let inconsistent: Vec<usize> = fragments[self.threshold..]
+            .iter()
+            .filter(|(x, y)| interpolate(*x, &xs, &ys) != *y)
+            .map(|(x, _)| *x as usize)
+            .collect();
+        if !inconsistent.is_empty() {
+            return Err(VssError::InconsistentShares(inconsistent));
+        }
+        let columns: Vec<Vec<u8>> = (1..=self.threshold as u8)
+            .map(|x| match xs.iter().position(|x_i| *x_i == x) {
+                Some(i) => ys[i].clone(),
+                None => interpolate(x, &xs, &ys),
+            })
+            .collect();
+        let rows: Vec<Vec<u8>> = (0..length)
+            .map(|k| columns.iter().map(|column| column[k]).collect())
+            .collect();
+        decode_chunks(&rows)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{thread_rng, Rng};
+
+    #[test]
+    fn test_ida_3_of_6_works() {
+        let ida = InformationDispersal {
+            threshold: 3,
+            fragment_amount: 6,
+        };
+        let mut data = vec![0u8; 1000];
+        thread_rng().fill(&mut data[..]);
+        let fragments = ida.disperse(&data);
+        assert_eq!(fragments.len(), 6);
+        for (_, fragment) in fragments.iter() {
+            assert_eq!(fragment.len(), (data.len() + 8).div_ceil(3));
+        }
+        // the code is systematic: the first fragments interleave the framed data
+        assert_eq!(&fragments[0].1[3..6], &[data[1], data[4], data[7]]);
+        assert_eq!(ida.reconstruct(&fragments[3..]), data);
+        assert_eq!(
+            ida.reconstruct(&[
+                fragments[5].clone(),
+                fragments[0].clone(),
+                fragments[3].clone()
+            ]),
+            data
+        );
+        assert_eq!(ida.reconstruct(&fragments), data);
+        assert_eq!(ida.reconstruct(&ida.disperse(b"")[2..5]), Vec::<u8>::new());
+    }
+
+    #[test]
+    fn test_ida_reports_bad_fragments() {
+        let ida = InformationDispersal {
+            threshold: 2,
+            fragment_amount: 4,
+        };
+        let mut fragments = ida.disperse(b"a blob for the storage layer");
+        fragments[3].1[4] ^= 0x80;
+        assert_eq!(
+            ida.try_reconstruct(&fragments).unwrap_err(),
+            VssError::InconsistentShares(vec![4])
+        );
+        fragments[2].1.pop();
+        assert_eq!(
+            ida.try_reconstruct(&fragments[1..3]).unwrap_err(),
+            VssError::InconsistentShares(vec![3])
+        );
+        assert_eq!(
+            ida.try_reconstruct(&fragments[..1]).unwrap_err(),
+            VssError::TooFewShares {
+                required: 2,
+                provided: 1
+            }
+        );
+        let invalid = InformationDispersal {
+            threshold: 5,
+            fragment_amount: 4,
+        };
+        assert_eq!(
+            invalid.try_disperse(b"blob").unwrap_err(),
+            VssError::InvalidThreshold {
+                threshold: 5,
+                share_amount: 4
+            }
+        );
+    }
+}
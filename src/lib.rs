@@ -34,10 +34,7 @@
 //! use verifiable_secret_sharing::Secp256k1Scalar;
 //! # fn main(){
 //! let secret: Secp256k1Scalar = Secp256k1Scalar::from_hex(b"7613c39ea009afd24ccf8c25f13591377091297b20a48ecaad0e92618d36dcc6");
-//! let vss = VerifiableSecretSharing {
-//!     threshold: 3,
-//!     share_amount: 5,
-//! };
+//! let vss = VerifiableSecretSharing::new(3, 5);
 //! let (shares, commitments) = vss.split(&secret);
 //! let sub_shares = &shares[0..3];
 //! let recovered = vss.recover(&sub_shares);
@@ -47,10 +44,91 @@
 //! }
 //! # }
 //! ```
+//!
+//! ### pedersen's verifiable secret sharing
+//!
+//! ```rust
+//! use verifiable_secret_sharing::PedersenVss;
+//! use verifiable_secret_sharing::{Secp256k1, Secp256k1Scalar};
+//! # fn main(){
+//! let secret: Secp256k1Scalar = Secp256k1Scalar::new_random();
+//! let vss = PedersenVss {
+//!     threshold: 3,
+//!     share_amount: 5,
+//!     curve: Secp256k1,
+//! };
+//! let (shares, commitments) = vss.split(&secret);
+//! assert_eq!(secret, vss.recover(&shares[0..3]));
+//! for share in shares {
+//!     assert!(PedersenVss::verify(share, &commitments))
+//! }
+//! # }
+//! ```
+pub use bls12_381_helper::{Bls12381G1, Bls12381G2};
+pub use chunked::ChunkedShare;
+pub use curve::{Curve, Point, Scalar, Secp256k1};
+pub use curve25519_helper::{Ed25519, Ristretto255};
+pub use dkg::{
+    qualified_dealers, Complaint, ComplaintAnswer, DealerBroadcast, DkgOutput, DkgParticipant,
+    DkgSimulation,
+};
+pub use dleq::{DleqProof, DleqStatement};
+pub use errors::VssError;
 pub use feldman_vss::VerifiableSecretSharing;
-pub use secp256k1_helper::{Secp256k1Point, Secp256k1Scalar};
+pub use field::{BigIntField, Prime256Field, PrimeField, ScalarField};
+pub use frost::{
+    aggregate as frost_aggregate, verify_share as frost_verify_share,
+    verify_signature as frost_verify_signature, FrostSigner, NonceCommitment, SchnorrSignature,
+    SignatureShare, SigningNonces, SigningPackage,
+};
+pub use gf256_sss::Gf256SecretSharing;
+pub use gjkr::{
+    gjkr_qualified_dealers, ExtractionComplaint, GjkrParticipant, GjkrPhase, GjkrSimulation,
+    PedersenBroadcast, PedersenComplaintAnswer, RevealedShare,
+};
+pub use ida::InformationDispersal;
+pub use p256_helper::P256;
+pub use pedersen_vss::PedersenVss;
+pub use pvss::{
+    Ciphertext, CutAndChooseRound, DecryptedShare, EncryptedShare, SchoenmakersDistribution,
+    SchoenmakersPvss, StadlerPvss, STADLER_ROUNDS,
+};
+pub use secp256k1_helper::{PrecomputedPoint, Secp256k1Point, Secp256k1Scalar};
 pub use simple_sss::ShamirSecretSharing;
+pub use ssms::{SecretSharingMadeShort, SsmsShare};
+pub use threshold_bls::{
+    bls_partial_sign, combine_bls_partial_signatures, verify_bls_partial_signature,
+    verify_bls_signature, BlsPartialSignature,
+};
+pub use threshold_ecdsa::{
+    EcdsaDealerShares, EcdsaDealing, EcdsaSignatureShare, EcdsaSigner, ProductShare,
+};
+pub use threshold_elgamal::{
+    combine_partial_decryptions, encrypt_to_group, encrypt_to_group_with_rng, partial_decrypt,
+    verify_partial_decryption, ElGamalCiphertext, PartialDecryption,
+};
+pub use transcript::Transcript;
 
+mod bls12_381_helper;
+mod chunked;
+mod curve;
+mod curve25519_helper;
+mod dkg;
+mod dleq;
+mod errors;
 mod feldman_vss;
+mod field;
+mod frost;
+mod gf256_sss;
+mod gjkr;
+mod ida;
+mod p256_helper;
+mod pedersen_vss;
+mod pvss;
 mod secp256k1_helper;
 mod simple_sss;
+mod ssms;
+mod threshold_bls;
+mod threshold_ecdsa;
+mod threshold_elgamal;
+mod transcript;
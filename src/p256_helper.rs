@@ -0,0 +1,165 @@
+use crate::curve::{Curve, Point, Scalar};
+use p256::elliptic_curve::group::Group;
+use p256::elliptic_curve::sec1::{FromEncodedPoint, ToEncodedPoint};
+use p256::elliptic_curve::{Field, PrimeField};
+use p256::{AffinePoint, EncodedPoint, FieldBytes, ProjectivePoint, Scalar as P256Scalar};
+use rand::Rng;
+use sha2::{Digest, Sha256};
+
+/// The NIST P-256 curve, backed by the `p256` crate.
+///
+/// Points are encoded as 33 bytes SEC1 compressed points, the identity as
+/// the single SEC1 byte `0x00`.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub struct P256;
+
+impl Curve for P256 {
+    type Scalar = P256Scalar;
+    type Point = ProjectivePoint;
+}
+
+impl Scalar for P256Scalar {
+    fn zero() -> Self {
+        P256Scalar::ZERO
+    }
+
+    fn one() -> Self {
+        P256Scalar::ONE
+    }
+
+    fn from_u64(n: u64) -> Self {
+        P256Scalar::from(n)
+    }
+
+    fn random<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        loop {
+            let mut bytes = FieldBytes::default();
+            rng.fill(&mut bytes[..]);
+            let scalar: Option<P256Scalar> = P256Scalar::from_repr(bytes).into();
+            match scalar {
+                Some(scalar) if !bool::from(scalar.is_zero()) => return scalar,
+                _ => continue,
+            }
+        }
+    }
+
+    fn inverse(&self) -> Self {
+        self.invert().unwrap_or(P256Scalar::ZERO)
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        self.to_repr().to_vec()
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() != 32 {
+            return None;
+        }
+        let mut repr = FieldBytes::default();
+        repr.copy_from_slice(bytes);
+        P256Scalar::from_repr(repr).into()
+    }
+}
+
+impl Point for ProjectivePoint {
+    type Scalar = P256Scalar;
+    type Curve = P256;
+
+    fn generator() -> Self {
+        <ProjectivePoint as Group>::generator()
+    }
+
+    fn identity() -> Self {
+        <ProjectivePoint as Group>::identity()
+    }
+
+    /// Uses try-and-increment: `SHA256(data || counter)` is taken as an x
+    /// coordinate with even y until it lands on the curve.
+    fn hash_to_curve(data: &[u8]) -> Self {
+        (0u32..)
+            .find_map(|counter| {
+                let mut hasher = Sha256::new();
+                hasher.update(data);
+                hasher.update(counter.to_be_bytes());
+                let mut v = vec![2_u8];
+                v.extend(hasher.finalize());
+                <ProjectivePoint as Point>::from_bytes(&v)
+            })
+            .unwrap()
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        self.to_affine().to_encoded_point(true).as_bytes().to_vec()
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let encoded = EncodedPoint::from_bytes(bytes).ok()?;
+        if !encoded.is_compressed() && !encoded.is_identity() {
+            return None;
+        }
+        let point: Option<AffinePoint> = AffinePoint::from_encoded_point(&encoded).into();
+        point.map(ProjectivePoint::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curve::tests::{encoding_round_trips, split_verify_recover};
+    use crate::feldman_vss::VerifiableSecretSharing;
+    use p256::ecdsa::signature::{Signer, Verifier};
+    use p256::ecdsa::{Signature, SigningKey};
+    use rand::thread_rng;
+
+    #[test]
+    fn test_p256_backend() {
+        split_verify_recover(P256);
+        encoding_round_trips::<P256>();
+    }
+
+    #[test]
+    fn test_p256_commitments_are_sec1_compressed() {
+        let vss = VerifiableSecretSharing {
+            threshold: 2,
+            share_amount: 3,
+            curve: P256,
+        };
+        let (_, commitments) = vss.split(&<P256Scalar as Scalar>::random(&mut thread_rng()));
+        for commitment in commitments.iter() {
+            let bytes = Point::to_bytes(commitment);
+            assert_eq!(bytes.len(), 33);
+            assert!(bytes[0] == 2 || bytes[0] == 3);
+        }
+        let uncompressed = commitments[0].to_affine().to_encoded_point(false);
+        assert_eq!(
+            <ProjectivePoint as Point>::from_bytes(uncompressed.as_bytes()),
+            None
+        );
+    }
+
+    #[test]
+    fn test_p256_recovered_key_signs() {
+        let secret = <P256Scalar as Scalar>::random(&mut thread_rng());
+        let signing_key = SigningKey::from_bytes(&secret.to_repr()).unwrap();
+        let verifying_key = signing_key.verifying_key();
+        let vss = VerifiableSecretSharing {
+            threshold: 3,
+            share_amount: 5,
+            curve: P256,
+        };
+        let (shares, commitments) = vss.split(&secret);
+        assert_eq!(
+            Point::to_bytes(&commitments[0]),
+            verifying_key.to_encoded_point(true).as_bytes()
+        );
+        for share in shares.iter() {
+            assert!(VerifiableSecretSharing::verify(*share, &commitments));
+        }
+
+        let recovered = vss.recover(&shares[2..]);
+        let recovered_key = SigningKey::from_bytes(&recovered.to_repr()).unwrap();
+        let message = b"signed by a recovered key";
+        let signature: Signature = recovered_key.sign(message);
+        assert!(verifying_key.verify(message, &signature).is_ok());
+    }
+}
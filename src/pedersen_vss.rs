@@ -0,0 +1,249 @@
+use crate::curve::{Curve, Point, Scalar, Secp256k1};
+use crate::errors::VssError;
+use crate::feldman_vss::VerifiableSecretSharing;
+use crate::secp256k1_helper::{PrecomputedPoint, Secp256k1Scalar};
+use rand::{thread_rng, Rng};
+use std::any::Any;
+use std::sync::OnceLock;
+
+type SharesAndCommitments<C> = (
+    Vec<(usize, <C as Curve>::Scalar, <C as Curve>::Scalar)>,
+    Vec<<C as Curve>::Point>,
+);
+
+/// The `PedersenVss` stores the parameters of Pedersen's verifiable secret sharing
+/// over the curve `C`, secp256k1 by default.
+///
+/// Unlike Feldman's scheme the commitments `g^a_i * h^b_i` are blinded by a
+/// second random polynomial, so they reveal nothing about the secret.
+pub struct PedersenVss<C: Curve = Secp256k1> {
+    /// the threshold of shares to recover the secret.
+    pub threshold: usize,
+    /// the total number of shares to generate from the secret.
+    pub share_amount: usize,
+    /// the curve of the shares and commitments.
+    pub curve: C,
+}
+
+impl<C: Curve> PedersenVss<C> {
+    /// Split the secret to `(index, share, blinding_share)` triples and commitments.
+    ///
+    /// Panics if the threshold is invalid, see `try_split`.
+    pub fn split(&self, secret: &C::Scalar) -> SharesAndCommitments<C> {
+        self.try_split(secret).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Split the secret to `(index, share, blinding_share)` triples and commitments,
+    /// failing on an invalid threshold.
+    pub fn try_split(&self, secret: &C::Scalar) -> Result<SharesAndCommitments<C>, VssError> {
+        self.try_split_with_rng(secret, &mut thread_rng())
+    }
+
+    /// Split the secret to `(index, share, blinding_share)` triples and commitments,
+    /// sampling both polynomials from `rng`.
+    pub fn try_split_with_rng<R: Rng + ?Sized>(
+        &self,
+        secret: &C::Scalar,
+        rng: &mut R,
+    ) -> Result<SharesAndCommitments<C>, VssError> {
+        self.deal_with_rng(secret, rng).map(|(dealing, _)| dealing)
+    }
+
+    /// Split the secret like `try_split_with_rng`, also returning the secret polynomial.
+    pub(crate) fn deal_with_rng<R: Rng + ?Sized>(
+        &self,
+        secret: &C::Scalar,
+        rng: &mut R,
+    ) -> Result<(SharesAndCommitments<C>, Vec<C::Scalar>), VssError> {
+        if self.threshold == 0 || self.threshold > self.share_amount {
+            return Err(VssError::InvalidThreshold {
+                threshold: self.threshold,
+                share_amount: self.share_amount,
+            });
+        }
+        let vss = self.feldman();
+        let polynomial = vss.sample_polynomial_with_rng(secret, rng);
+        let blinding_secret = C::Scalar::random(rng);
+        let blinding_polynomial = vss.sample_polynomial_with_rng(&blinding_secret, rng);
+        let shares = vss
+            .evaluate_polynomial(&polynomial)
+            .into_iter()
+            .zip(vss.evaluate_polynomial(&blinding_polynomial))
+            .map(|((index, share), (_, blinding_share))| (index, share, blinding_share))
+            .collect();
+        let commitments = Self::generate_commitments(&polynomial, &blinding_polynomial);
+        Ok(((shares, commitments), polynomial))
+    }
+
+    /// Recover the secret by at least threshold shares, the blinding shares are ignored.
+    ///
+    /// Panics if the shares are invalid or inconsistent, see `try_recover`.
+    pub fn recover(&self, shares: &[(usize, C::Scalar, C::Scalar)]) -> C::Scalar {
+        self.try_recover(shares).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Recover the secret by at least threshold shares, the blinding shares are ignored.
+    pub fn try_recover(
+        &self,
+        shares: &[(usize, C::Scalar, C::Scalar)],
+    ) -> Result<C::Scalar, VssError> {
+        let secret_shares: Vec<(usize, C::Scalar)> = shares
+            .iter()
+            .map(|(index, share, _)| (*index, *share))
+            .collect();
+        self.feldman().try_recover(&secret_shares)
+    }
+
+    fn feldman(&self) -> VerifiableSecretSharing<C> {
+        VerifiableSecretSharing {
+            threshold: self.threshold,
+            share_amount: self.share_amount,
+            curve: self.curve,
+        }
+    }
+}
+
+/// The associated functions take the points of the curve `C`, which is
+/// inferred from the point type of the arguments through `Point::Curve`.
+impl<C: Curve> PedersenVss<C> {
+    /// The second generator `h`, hashed to the curve from the standard generator
+    /// so that nobody knows `log_g(h)`.
+    pub fn second_generator<P: Point<Curve = C>>() -> P {
+        let mut seed = b"verifiable_secret_sharing/pedersen/h".to_vec();
+        seed.extend(P::generator().to_bytes());
+        P::hash_to_curve(&seed)
+    }
+
+    /// Verify a specific share triple distributed by the dealer is valid.
+    ///
+    /// Malformed input, a zero share index or empty commitments, does not
+    /// verify; `try_verify` tells it apart from an invalid share.
+    pub fn verify<P: Point<Curve = C>>(
+        share: (usize, P::Scalar, P::Scalar),
+        commitments: &[P],
+    ) -> bool {
+        Self::try_verify(share, commitments).unwrap_or(false)
+    }
+
+    /// Verify a specific share triple distributed by the dealer is valid,
+    /// failing on a zero share index or empty commitments.
+    pub fn try_verify<P: Point<Curve = C>>(
+        share: (usize, P::Scalar, P::Scalar),
+        commitments: &[P],
+    ) -> Result<bool, VssError> {
+        let (share_index, share_value, blinding_value) = share;
+        let share_index_commitment =
+            VerifiableSecretSharing::evaluate_commitments(commitments, share_index)?;
+        let share_value_commitment =
+            P::mul_generator(&share_value) + Self::mul_second_generator(&blinding_value);
+        Ok(share_value_commitment == share_index_commitment)
+    }
+
+    /// Calculate `second_generator() * scalar`, with the cached table of `h`
+    /// on secp256k1.
+    fn mul_second_generator<P: Point<Curve = C>>(scalar: &P::Scalar) -> P {
+        (scalar as &dyn Any)
+            .downcast_ref::<Secp256k1Scalar>()
+            .map(|scalar| second_generator_table().mul(scalar))
+            .and_then(|h| (&h as &dyn Any).downcast_ref::<P>().copied())
+            .unwrap_or_else(|| Self::second_generator::<P>() * *scalar)
+    }
+
+    fn generate_commitments<P: Point<Curve = C>>(
+        polynomial: &[P::Scalar],
+        blinding_polynomial: &[P::Scalar],
+    ) -> Vec<P> {
+        polynomial
+            .iter()
+            .zip(blinding_polynomial.iter())
+            .map(|(a, b)| P::mul_generator(a) + Self::mul_second_generator(b))
+            .collect()
+    }
+}
+
+/// The table of the secp256k1 second generator `h`, built on first use.
+fn second_generator_table() -> &'static PrecomputedPoint {
+    static TABLE: OnceLock<PrecomputedPoint> = OnceLock::new();
+    TABLE.get_or_init(|| PrecomputedPoint::new(PedersenVss::second_generator()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::secp256k1_helper::{Secp256k1Point, Secp256k1Scalar};
+
+    #[test]
+    fn test_pedersen_3_of_5_works() {
+        let secret: Secp256k1Scalar = Secp256k1Scalar::new_random();
+        let vss = PedersenVss {
+            threshold: 3,
+            share_amount: 5,
+            curve: Secp256k1,
+        };
+        let (shares, commitments) = vss.split(&secret);
+        assert_eq!(secret, vss.recover(&shares[1..4]));
+        for share in shares {
+            assert!(PedersenVss::verify(share, &commitments))
+        }
+    }
+
+    #[test]
+    fn test_pedersen_rejects_forged_blinding_share() {
+        let secret: Secp256k1Scalar = Secp256k1Scalar::new_random();
+        let vss = PedersenVss {
+            threshold: 2,
+            share_amount: 3,
+            curve: Secp256k1,
+        };
+        let (shares, commitments) = vss.split(&secret);
+        let (index, share, blinding_share) = shares[0];
+        let forged = (index, share, blinding_share + Secp256k1Scalar::one());
+        assert!(!PedersenVss::verify(forged, &commitments));
+    }
+
+    #[test]
+    fn test_pedersen_verify_rejects_malformed_input() {
+        let vss = PedersenVss {
+            threshold: 2,
+            share_amount: 3,
+            curve: Secp256k1,
+        };
+        let (shares, commitments) = vss.split(&Secp256k1Scalar::new_random());
+        let (_, share, blinding_share) = shares[0];
+        assert!(!PedersenVss::verify(
+            (0, share, blinding_share),
+            &commitments
+        ));
+        assert!(!PedersenVss::verify::<Secp256k1Point>(shares[0], &[]));
+    }
+
+    #[test]
+    fn test_pedersen_commitments_hide_secret() {
+        let secret: Secp256k1Scalar = Secp256k1Scalar::new_random();
+        let vss = PedersenVss {
+            threshold: 2,
+            share_amount: 3,
+            curve: Secp256k1,
+        };
+        let (_, commitments) = vss.split(&secret);
+        assert_ne!(commitments[0], Secp256k1Point::generator() * secret);
+    }
+
+    #[test]
+    fn test_second_generator_is_fixed() {
+        let h: Secp256k1Point = PedersenVss::second_generator();
+        assert_eq!(h, PedersenVss::second_generator());
+        assert_ne!(h, Secp256k1Point::generator());
+    }
+
+    #[test]
+    fn test_second_generator_table_matches_hashed_h() {
+        let h: Secp256k1Point = PedersenVss::second_generator();
+        assert_eq!(second_generator_table().point(), h);
+        let scalar = Secp256k1Scalar::new_random();
+        assert_eq!(
+            PedersenVss::mul_second_generator::<Secp256k1Point>(&scalar),
+            h * scalar
+        );
+    }
+}
@@ -0,0 +1,6 @@
+//! Publicly verifiable secret sharing, where anyone can check the dealer's work.
+mod schoenmakers;
+mod stadler;
+
+pub use schoenmakers::{DecryptedShare, SchoenmakersDistribution, SchoenmakersPvss};
+pub use stadler::{Ciphertext, CutAndChooseRound, EncryptedShare, StadlerPvss, STADLER_ROUNDS};
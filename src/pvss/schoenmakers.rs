@@ -0,0 +1,353 @@
+use crate::curve::Secp256k1;
+use crate::dleq::{DleqProof, DleqStatement};
+use crate::errors::{check_share_indices, VssError};
+use crate::feldman_vss::VerifiableSecretSharing;
+use crate::secp256k1_helper::{Secp256k1Point, Secp256k1Scalar};
+use crate::transcript::Transcript;
+
+/// Everything the dealer publishes in Schoenmakers' scheme.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct SchoenmakersDistribution {
+    /// the Feldman commitments `C_j = g^a_j`.
+    pub commitments: Vec<Secp256k1Point>,
+    /// the encrypted shares `(i, Y_i = y_i^p(i))`.
+    pub encrypted_shares: Vec<(usize, Secp256k1Point)>,
+    /// the batched proof that `log_g(X_i) == log_{y_i}(Y_i)` for every `i`.
+    pub proof: DleqProof,
+}
+
+/// A share `S_i = G^p(i)` decrypted by its holder, with a proof of correct decryption.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct DecryptedShare {
+    /// the index of the share.
+    pub index: usize,
+    /// the decrypted share `S_i`.
+    pub value: Secp256k1Point,
+    /// the proof that `log_G(y_i) == log_{S_i}(Y_i)`.
+    pub proof: DleqProof,
+}
+
+/// The `SchoenmakersPvss` stores the parameters of Schoenmakers' publicly verifiable secret sharing.
+///
+/// The dealer encrypts `p(i)` in the exponent of the holder's public key
+/// `y_i = G^x_i`, holders decrypt to `S_i = G^p(i)`, and the shared secret
+/// `G^s` is reconstructed by Lagrange interpolation in the exponent.
+pub struct SchoenmakersPvss {
+    /// the threshold of shares to recover the secret.
+    pub threshold: usize,
+    /// the total number of shares to generate from the secret.
+    pub share_amount: usize,
+}
+
+impl SchoenmakersPvss {
+    /// The generator `G` of participant keys and of the shared secret `G^s`,
+    /// hashed to the curve so that nobody knows `log_g(G)`.
+    pub fn secret_generator() -> Secp256k1Point {
+        let mut seed = b"verifiable_secret_sharing/schoenmakers/G".to_vec();
+        seed.extend(Secp256k1Point::generator().serialize().iter());
+        Secp256k1Point::hash_to_curve(&seed)
+    }
+
+    /// Split the secret and encrypt the `i`th share to `public_keys[i - 1]`.
+    ///
+    /// Panics if the parameters are invalid, see `try_split`.
+    pub fn split(
+        &self,
+        secret: &Secp256k1Scalar,
+        public_keys: &[Secp256k1Point],
+    ) -> SchoenmakersDistribution {
+        self.try_split(secret, public_keys)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Split the secret and encrypt the `i`th share to `public_keys[i - 1]`,
+    /// failing on an invalid threshold or a wrong number of public keys.
+    pub fn try_split(
+        &self,
+        secret: &Secp256k1Scalar,
+        public_keys: &[Secp256k1Point],
+    ) -> Result<SchoenmakersDistribution, VssError> {
+        if public_keys.len() != self.share_amount {
+            return Err(VssError::WrongPublicKeyCount {
+                expected: self.share_amount,
+                actual: public_keys.len(),
+            });
+        }
+        let (shares, commitments) = self.feldman().try_split(secret)?;
+        let encrypted_shares: Vec<(usize, Secp256k1Point)> = shares
+            .iter()
+            .zip(public_keys.iter())
+            .map(|((index, share), public_key)| (*index, *public_key * *share))
+            .collect();
+        let statements =
+            Self::distribution_statements(&commitments, &encrypted_shares, public_keys)?;
+        let witnesses: Vec<Secp256k1Scalar> = shares.iter().map(|(_, share)| *share).collect();
+        let mut transcript = Self::distribution_transcript(&commitments);
+        let proof = DleqProof::prove(&mut transcript, &statements, &witnesses);
+        Ok(SchoenmakersDistribution {
+            commitments,
+            encrypted_shares,
+            proof,
+        })
+    }
+
+    /// Verify, without any secret, that every encrypted share is consistent
+    /// with the dealer's commitments and that the `i`th one is the share at
+    /// index `i`.
+    pub fn verify_distribution(
+        &self,
+        distribution: &SchoenmakersDistribution,
+        public_keys: &[Secp256k1Point],
+    ) -> bool {
+        if distribution.commitments.len() != self.threshold
+            || distribution.encrypted_shares.len() != self.share_amount
+            || public_keys.len() != self.share_amount
+        {
+            return false;
+        }
+        // every holder must get a distinct evaluation point, its own index
+        if distribution
+            .encrypted_shares
+            .iter()
+            .enumerate()
+            .any(|(i, (index, _))| *index != i + 1)
+        {
+            return false;
+        }
+        match Self::distribution_statements(
+            &distribution.commitments,
+            &distribution.encrypted_shares,
+            public_keys,
+        ) {
+            Ok(statements) => {
+                let mut transcript = Self::distribution_transcript(&distribution.commitments);
+                distribution.proof.verify(&mut transcript, &statements)
+            }
+            Err(_) => false,
+        }
+    }
+
+    /// Decrypt an encrypted share with the holder's secret key and prove it was done correctly.
+    pub fn decrypt_share(
+        encrypted_share: (usize, Secp256k1Point),
+        secret_key: &Secp256k1Scalar,
+    ) -> DecryptedShare {
+        let (index, encrypted_value) = encrypted_share;
+        let value = encrypted_value * secret_key.inv();
+        let statement = DleqStatement {
+            g1: Self::secret_generator(),
+            h1: Self::secret_generator() * *secret_key,
+            g2: value,
+            h2: encrypted_value,
+        };
+        let mut transcript = Self::decryption_transcript(index);
+        let proof = DleqProof::prove(&mut transcript, &[statement], &[*secret_key]);
+        DecryptedShare {
+            index,
+            value,
+            proof,
+        }
+    }
+
+    /// Verify a decrypted share against the encrypted share and the holder's public key.
+    pub fn verify_decrypted_share(
+        share: &DecryptedShare,
+        encrypted_share: (usize, Secp256k1Point),
+        public_key: &Secp256k1Point,
+    ) -> bool {
+        let (index, encrypted_value) = encrypted_share;
+        if share.index != index {
+            return false;
+        }
+        let statement = DleqStatement {
+            g1: Self::secret_generator(),
+            h1: *public_key,
+            g2: share.value,
+            h2: encrypted_value,
+        };
+        let mut transcript = Self::decryption_transcript(index);
+        share.proof.verify(&mut transcript, &[statement])
+    }
+
+    /// Recover the shared secret `G^s` by at least threshold verified decrypted shares.
+    pub fn recover(&self, shares: &[DecryptedShare]) -> Result<Secp256k1Point, VssError> {
+        if self.threshold == 0 || shares.len() < self.threshold {
+            return Err(VssError::TooFewShares {
+                required: self.threshold,
+                provided: shares.len(),
+            });
+        }
+        let points: Vec<(usize, Secp256k1Point)> = shares[..self.threshold]
+            .iter()
+            .map(|share| (share.index, share.value))
+            .collect();
+        check_share_indices(&points)?;
+        let (xs, values): (Vec<usize>, Vec<Secp256k1Point>) = points.into_iter().unzip();
+        let coefficients = VerifiableSecretSharing::<Secp256k1>::lagrange_coefficients(
+            Secp256k1Scalar::zero(),
+            &xs,
+        );
+        Ok(Secp256k1Point::multiscalar_mul(&coefficients, &values))
+    }
+
+    fn distribution_statements(
+        commitments: &[Secp256k1Point],
+        encrypted_shares: &[(usize, Secp256k1Point)],
+        public_keys: &[Secp256k1Point],
+    ) -> Result<Vec<DleqStatement>, VssError> {
+        encrypted_shares
+            .iter()
+            .zip(public_keys.iter())
+            .map(|((index, encrypted_value), public_key)| {
+                Ok(DleqStatement {
+                    g1: Secp256k1Point::generator(),
+                    h1: VerifiableSecretSharing::evaluate_commitments(commitments, *index)?,
+                    g2: *public_key,
+                    h2: *encrypted_value,
+                })
+            })
+            .collect()
+    }
+
+    fn distribution_transcript(commitments: &[Secp256k1Point]) -> Transcript {
+        let mut transcript =
+            Transcript::new(b"verifiable_secret_sharing/schoenmakers/distribution");
+        for commitment in commitments {
+            transcript.append_point(b"commitment", commitment);
+        }
+        transcript
+    }
+
+    fn decryption_transcript(index: usize) -> Transcript {
+        let mut transcript = Transcript::new(b"verifiable_secret_sharing/schoenmakers/decryption");
+        transcript.append_u64(b"index", index as u64);
+        transcript
+    }
+
+    fn feldman(&self) -> VerifiableSecretSharing {
+        VerifiableSecretSharing {
+            threshold: self.threshold,
+            share_amount: self.share_amount,
+            curve: Secp256k1,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key_pairs(amount: usize) -> Vec<(Secp256k1Scalar, Secp256k1Point)> {
+        (0..amount)
+            .map(|_| {
+                let secret_key = Secp256k1Scalar::new_random();
+                (
+                    secret_key,
+                    SchoenmakersPvss::secret_generator() * secret_key,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_schoenmakers_pvss_3_of_5_works() {
+        let secret: Secp256k1Scalar = Secp256k1Scalar::new_random();
+        let pvss = SchoenmakersPvss {
+            threshold: 3,
+            share_amount: 5,
+        };
+        let keys = key_pairs(5);
+        let public_keys: Vec<Secp256k1Point> = keys.iter().map(|(_, pk)| *pk).collect();
+        let distribution = pvss.split(&secret, &public_keys);
+        assert!(pvss.verify_distribution(&distribution, &public_keys));
+
+        let decrypted: Vec<DecryptedShare> = distribution
+            .encrypted_shares
+            .iter()
+            .zip(keys.iter())
+            .map(|(encrypted, (secret_key, _))| {
+                SchoenmakersPvss::decrypt_share(*encrypted, secret_key)
+            })
+            .collect();
+        for ((share, encrypted), public_key) in decrypted
+            .iter()
+            .zip(distribution.encrypted_shares.iter())
+            .zip(public_keys.iter())
+        {
+            assert!(SchoenmakersPvss::verify_decrypted_share(
+                share, *encrypted, public_key
+            ));
+        }
+        let recovered = pvss.recover(&decrypted[1..4]).unwrap();
+        assert_eq!(recovered, SchoenmakersPvss::secret_generator() * secret);
+    }
+
+    #[test]
+    fn test_schoenmakers_pvss_rejects_bad_distribution() {
+        let pvss = SchoenmakersPvss {
+            threshold: 2,
+            share_amount: 3,
+        };
+        let keys = key_pairs(3);
+        let public_keys: Vec<Secp256k1Point> = keys.iter().map(|(_, pk)| *pk).collect();
+        let mut distribution = pvss.split(&Secp256k1Scalar::new_random(), &public_keys);
+        distribution.encrypted_shares[1].1 =
+            distribution.encrypted_shares[1].1 + Secp256k1Point::generator();
+        assert!(!pvss.verify_distribution(&distribution, &public_keys));
+    }
+
+    #[test]
+    fn test_schoenmakers_pvss_rejects_repeated_evaluation_point() {
+        let pvss = SchoenmakersPvss {
+            threshold: 2,
+            share_amount: 3,
+        };
+        let keys = key_pairs(3);
+        let public_keys: Vec<Secp256k1Point> = keys.iter().map(|(_, pk)| *pk).collect();
+        // a dealer proving correct encryptions of share 1 to both holders 1 and 2
+        let (shares, commitments) = pvss
+            .feldman()
+            .try_split(&Secp256k1Scalar::new_random())
+            .unwrap();
+        let witnesses = vec![shares[0].1, shares[0].1, shares[2].1];
+        let encrypted_shares: Vec<(usize, Secp256k1Point)> = [1, 1, 3]
+            .iter()
+            .zip(witnesses.iter())
+            .zip(public_keys.iter())
+            .map(|((index, share), public_key)| (*index, *public_key * *share))
+            .collect();
+        let statements = SchoenmakersPvss::distribution_statements(
+            &commitments,
+            &encrypted_shares,
+            &public_keys,
+        )
+        .unwrap();
+        let mut transcript = SchoenmakersPvss::distribution_transcript(&commitments);
+        let proof = DleqProof::prove(&mut transcript, &statements, &witnesses);
+        let distribution = SchoenmakersDistribution {
+            commitments,
+            encrypted_shares,
+            proof,
+        };
+        assert!(!pvss.verify_distribution(&distribution, &public_keys));
+    }
+
+    #[test]
+    fn test_schoenmakers_pvss_rejects_bad_decryption() {
+        let pvss = SchoenmakersPvss {
+            threshold: 2,
+            share_amount: 2,
+        };
+        let keys = key_pairs(2);
+        let public_keys: Vec<Secp256k1Point> = keys.iter().map(|(_, pk)| *pk).collect();
+        let distribution = pvss.split(&Secp256k1Scalar::new_random(), &public_keys);
+        let mut share =
+            SchoenmakersPvss::decrypt_share(distribution.encrypted_shares[0], &keys[0].0);
+        share.value = share.value + Secp256k1Point::generator();
+        assert!(!SchoenmakersPvss::verify_decrypted_share(
+            &share,
+            distribution.encrypted_shares[0],
+            &public_keys[0]
+        ));
+    }
+}
@@ -0,0 +1,332 @@
+use crate::curve::Secp256k1;
+use crate::errors::VssError;
+use crate::feldman_vss::VerifiableSecretSharing;
+use crate::secp256k1_helper::{Secp256k1Point, Secp256k1Scalar};
+use crate::transcript::Transcript;
+
+type EncryptedSharesAndCommitments = (Vec<EncryptedShare>, Vec<Secp256k1Point>);
+
+/// The number of cut-and-choose rounds in every encryption proof.
+///
+/// The proof is made non-interactive with Fiat-Shamir, so a cheating dealer
+/// may grind challenges offline; 128 rounds keep that at 2^-128.
+pub const STADLER_ROUNDS: usize = 128;
+
+/// A hashed ElGamal ciphertext of a scalar: `(g^r, m + H(y^r))`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Ciphertext {
+    /// the ephemeral key `g^r`.
+    pub ephemeral: Secp256k1Point,
+    /// the plaintext masked by the hashed shared key.
+    pub masked: Secp256k1Scalar,
+}
+
+/// One cut-and-choose round of the encryption proof.
+///
+/// The dealer encrypts a random `w` and `w - share`, then opens the one
+/// selected by the challenge bit.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct CutAndChooseRound {
+    /// the commitment `g^w` to the random mask.
+    pub witness_commitment: Secp256k1Point,
+    /// the encryptions of `w` and `w - share`.
+    pub ciphertexts: [Ciphertext; 2],
+    /// the plaintext of the challenged ciphertext.
+    pub opened_plaintext: Secp256k1Scalar,
+    /// the encryption randomness of the challenged ciphertext.
+    pub opened_randomness: Secp256k1Scalar,
+}
+
+/// A share encrypted to one participant, with a publicly checkable proof.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct EncryptedShare {
+    /// the index of the share.
+    pub index: usize,
+    /// the cut-and-choose rounds.
+    pub rounds: Vec<CutAndChooseRound>,
+}
+
+/// The `StadlerPvss` stores the parameters of Stadler's publicly verifiable secret sharing.
+///
+/// Shares are dealt as in Feldman's scheme, but each one is encrypted to its
+/// holder's public key together with a cut-and-choose proof that the
+/// ciphertext contains the discrete logarithm of `g^share`, the value any
+/// third party can compute from the commitments.
+pub struct StadlerPvss {
+    /// the threshold of shares to recover the secret.
+    pub threshold: usize,
+    /// the total number of shares to generate from the secret.
+    pub share_amount: usize,
+}
+
+impl StadlerPvss {
+    /// Split the secret and encrypt the `i`th share to `public_keys[i - 1]`.
+    ///
+    /// Panics if the parameters are invalid, see `try_split`.
+    pub fn split(
+        &self,
+        secret: &Secp256k1Scalar,
+        public_keys: &[Secp256k1Point],
+    ) -> EncryptedSharesAndCommitments {
+        self.try_split(secret, public_keys)
+            .unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Split the secret and encrypt the `i`th share to `public_keys[i - 1]`,
+    /// failing on an invalid threshold or a wrong number of public keys.
+    pub fn try_split(
+        &self,
+        secret: &Secp256k1Scalar,
+        public_keys: &[Secp256k1Point],
+    ) -> Result<EncryptedSharesAndCommitments, VssError> {
+        if public_keys.len() != self.share_amount {
+            return Err(VssError::WrongPublicKeyCount {
+                expected: self.share_amount,
+                actual: public_keys.len(),
+            });
+        }
+        let (shares, commitments) = self.feldman().try_split(secret)?;
+        let encrypted_shares = shares
+            .into_iter()
+            .zip(public_keys.iter())
+            .map(|((index, share), public_key)| {
+                Self::encrypt_share(index, &share, public_key, &commitments)
+            })
+            .collect();
+        Ok((encrypted_shares, commitments))
+    }
+
+    /// Verify, without any secret, that an encrypted share holds the share
+    /// committed to by the dealer for that index.
+    pub fn verify(
+        share: &EncryptedShare,
+        public_key: &Secp256k1Point,
+        commitments: &[Secp256k1Point],
+    ) -> bool {
+        let share_commitment =
+            match VerifiableSecretSharing::evaluate_commitments(commitments, share.index) {
+                Ok(point) => point,
+                Err(_) => return false,
+            };
+        if share.rounds.len() != STADLER_ROUNDS {
+            return false;
+        }
+        let challenge = Self::challenge(share.index, public_key, commitments, &share.rounds);
+        share.rounds.iter().enumerate().all(|(j, round)| {
+            let bit = challenge_bit(&challenge, j);
+            let reencrypted = encrypt(
+                public_key,
+                &round.opened_plaintext,
+                &round.opened_randomness,
+            );
+            let opened_commitment = Secp256k1Point::generator() * round.opened_plaintext;
+            let expected_witness = if bit == 0 {
+                opened_commitment
+            } else {
+                opened_commitment + share_commitment
+            };
+            reencrypted == round.ciphertexts[bit] && expected_witness == round.witness_commitment
+        })
+    }
+
+    /// Decrypt an encrypted share with the holder's secret key.
+    ///
+    /// The result is checked against the commitments, so a decrypted share is
+    /// always valid.
+    pub fn decrypt(
+        share: &EncryptedShare,
+        secret_key: &Secp256k1Scalar,
+        commitments: &[Secp256k1Point],
+    ) -> Result<(usize, Secp256k1Scalar), VssError> {
+        let share_commitment =
+            VerifiableSecretSharing::evaluate_commitments(commitments, share.index)?;
+        share
+            .rounds
+            .iter()
+            .map(|round| {
+                decrypt(&round.ciphertexts[0], secret_key)
+                    - decrypt(&round.ciphertexts[1], secret_key)
+            })
+            .find(|value| Secp256k1Point::generator() * *value == share_commitment)
+            .map(|value| (share.index, value))
+            .ok_or(VssError::DecryptionFailed(share.index))
+    }
+
+    /// Recover the secret by at least threshold decrypted shares.
+    pub fn recover(&self, shares: &[(usize, Secp256k1Scalar)]) -> Secp256k1Scalar {
+        self.feldman().recover(shares)
+    }
+
+    fn encrypt_share(
+        index: usize,
+        share: &Secp256k1Scalar,
+        public_key: &Secp256k1Point,
+        commitments: &[Secp256k1Point],
+    ) -> EncryptedShare {
+        let generator = Secp256k1Point::generator();
+        let masks: Vec<(Secp256k1Scalar, [Secp256k1Scalar; 2])> = (0..STADLER_ROUNDS)
+            .map(|_| {
+                (
+                    Secp256k1Scalar::new_random(),
+                    [Secp256k1Scalar::new_random(), Secp256k1Scalar::new_random()],
+                )
+            })
+            .collect();
+        let mut rounds: Vec<CutAndChooseRound> = masks
+            .iter()
+            .map(|(mask, randomness)| CutAndChooseRound {
+                witness_commitment: generator * *mask,
+                ciphertexts: [
+                    encrypt(public_key, mask, &randomness[0]),
+                    encrypt(public_key, &(*mask - *share), &randomness[1]),
+                ],
+                opened_plaintext: Secp256k1Scalar::zero(),
+                opened_randomness: Secp256k1Scalar::zero(),
+            })
+            .collect();
+        let challenge = Self::challenge(index, public_key, commitments, &rounds);
+        for (j, (round, (mask, randomness))) in rounds.iter_mut().zip(masks.iter()).enumerate() {
+            let bit = challenge_bit(&challenge, j);
+            round.opened_plaintext = if bit == 0 { *mask } else { *mask - *share };
+            round.opened_randomness = randomness[bit];
+        }
+        EncryptedShare { index, rounds }
+    }
+
+    fn challenge(
+        index: usize,
+        public_key: &Secp256k1Point,
+        commitments: &[Secp256k1Point],
+        rounds: &[CutAndChooseRound],
+    ) -> [u8; 32] {
+        let mut transcript = Transcript::new(b"verifiable_secret_sharing/stadler_pvss");
+        transcript.append_u64(b"index", index as u64);
+        transcript.append_point(b"public key", public_key);
+        for commitment in commitments {
+            transcript.append_point(b"commitment", commitment);
+        }
+        for round in rounds {
+            transcript.append_point(b"witness commitment", &round.witness_commitment);
+            for ciphertext in round.ciphertexts.iter() {
+                transcript.append_point(b"ephemeral", &ciphertext.ephemeral);
+                transcript.append_scalar(b"masked", &ciphertext.masked);
+            }
+        }
+        transcript.challenge_bytes(b"challenge bits")
+    }
+
+    fn feldman(&self) -> VerifiableSecretSharing {
+        VerifiableSecretSharing {
+            threshold: self.threshold,
+            share_amount: self.share_amount,
+            curve: Secp256k1,
+        }
+    }
+}
+
+fn challenge_bit(challenge: &[u8; 32], round: usize) -> usize {
+    ((challenge[round / 8] >> (round % 8)) & 1) as usize
+}
+
+fn encrypt(
+    public_key: &Secp256k1Point,
+    plaintext: &Secp256k1Scalar,
+    randomness: &Secp256k1Scalar,
+) -> Ciphertext {
+    let shared_key = *public_key * *randomness;
+    Ciphertext {
+        ephemeral: Secp256k1Point::generator() * *randomness,
+        masked: *plaintext + Secp256k1Scalar::hash_to_scalar(&shared_key.serialize()),
+    }
+}
+
+fn decrypt(ciphertext: &Ciphertext, secret_key: &Secp256k1Scalar) -> Secp256k1Scalar {
+    let shared_key = ciphertext.ephemeral * *secret_key;
+    ciphertext.masked - Secp256k1Scalar::hash_to_scalar(&shared_key.serialize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key_pairs(amount: usize) -> Vec<(Secp256k1Scalar, Secp256k1Point)> {
+        (0..amount)
+            .map(|_| {
+                let secret_key = Secp256k1Scalar::new_random();
+                (secret_key, Secp256k1Point::generator() * secret_key)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_stadler_pvss_3_of_5_works() {
+        let secret: Secp256k1Scalar = Secp256k1Scalar::new_random();
+        let pvss = StadlerPvss {
+            threshold: 3,
+            share_amount: 5,
+        };
+        let keys = key_pairs(5);
+        let public_keys: Vec<Secp256k1Point> = keys.iter().map(|(_, pk)| *pk).collect();
+        let (encrypted_shares, commitments) = pvss.split(&secret, &public_keys);
+        for (share, public_key) in encrypted_shares.iter().zip(public_keys.iter()) {
+            assert!(StadlerPvss::verify(share, public_key, &commitments));
+        }
+        let shares: Vec<(usize, Secp256k1Scalar)> = encrypted_shares
+            .iter()
+            .zip(keys.iter())
+            .map(|(share, (secret_key, _))| {
+                StadlerPvss::decrypt(share, secret_key, &commitments).unwrap()
+            })
+            .collect();
+        for share in shares.iter() {
+            assert!(VerifiableSecretSharing::verify(*share, &commitments));
+        }
+        assert_eq!(secret, pvss.recover(&shares[2..5]));
+    }
+
+    #[test]
+    fn test_stadler_pvss_rejects_tampered_share() {
+        let secret: Secp256k1Scalar = Secp256k1Scalar::new_random();
+        let pvss = StadlerPvss {
+            threshold: 2,
+            share_amount: 2,
+        };
+        let keys = key_pairs(2);
+        let public_keys: Vec<Secp256k1Point> = keys.iter().map(|(_, pk)| *pk).collect();
+        let (encrypted_shares, commitments) = pvss.split(&secret, &public_keys);
+        assert!(!StadlerPvss::verify(
+            &encrypted_shares[0],
+            &public_keys[1],
+            &commitments
+        ));
+        let mut tampered = encrypted_shares[0].clone();
+        tampered.rounds[0].ciphertexts[1].masked =
+            tampered.rounds[0].ciphertexts[1].masked + Secp256k1Scalar::one();
+        assert!(!StadlerPvss::verify(
+            &tampered,
+            &public_keys[0],
+            &commitments
+        ));
+        assert_eq!(
+            StadlerPvss::decrypt(&encrypted_shares[0], &keys[1].0, &commitments).unwrap_err(),
+            VssError::DecryptionFailed(1)
+        );
+    }
+
+    #[test]
+    fn test_stadler_pvss_rejects_wrong_key_count() {
+        let pvss = StadlerPvss {
+            threshold: 2,
+            share_amount: 3,
+        };
+        let public_keys: Vec<Secp256k1Point> = key_pairs(2).iter().map(|(_, pk)| *pk).collect();
+        assert_eq!(
+            pvss.try_split(&Secp256k1Scalar::new_random(), &public_keys)
+                .unwrap_err(),
+            VssError::WrongPublicKeyCount {
+                expected: 3,
+                actual: 2
+            }
+        );
+    }
+}
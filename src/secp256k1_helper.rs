@@ -1,27 +1,186 @@
-use num_bigint_dig::traits::ModInverse;
+use k256::elliptic_curve::group::Curve;
+use k256::elliptic_curve::sec1::{FromEncodedPoint, ToEncodedPoint};
+use k256::elliptic_curve::subtle::{ConditionallySelectable, ConstantTimeEq};
+use k256::{AffinePoint, EncodedPoint, ProjectivePoint};
 use num_bigint_dig::BigInt;
-use num_bigint_dig::Sign::Plus;
-use num_integer::Integer;
+use num_bigint_dig::Sign::{Minus, Plus};
 use rand::{thread_rng, Rng};
 use secp256k1::constants::{CURVE_ORDER, GENERATOR_X, GENERATOR_Y, SECRET_KEY_SIZE};
-use secp256k1::{PublicKey, Secp256k1, SecretKey, VerifyOnly};
-use std::ops::{Add, Mul, Sub};
-use std::sync::Once;
-/// The `Secp256k1Scalar` is a scalar, wrapping the `SecretKey`
+use secp256k1::{PublicKey, Secp256k1, VerifyOnly};
+use sha2::{Digest, Sha256};
+use std::ops::{Add, Mul, Neg, Sub};
+use std::sync::OnceLock;
+/// The `Secp256k1Scalar` is a scalar modulo the curve order `n`, held as
+/// four little endian 64 bits limbs, always reduced
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
-pub struct Secp256k1Scalar(SecretKey);
+pub struct Secp256k1Scalar([u64; 4]);
 
-/// The `Secp256k1Point` is a point in elliptic curve, wrapping the `PublicKey`
+/// The `Secp256k1Point` is a point in elliptic curve, wrapping the `PublicKey`,
+/// or `None` for the point at infinity
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
-pub struct Secp256k1Point(PublicKey);
+pub struct Secp256k1Point(Option<PublicKey>);
 
 impl Secp256k1Point {
-    ///
+    /// Return the standard generator point `G` of secp256k1.
     pub fn generator() -> Secp256k1Point {
-        let mut v = vec![4 as u8];
-        v.extend(GENERATOR_X.as_ref());
-        v.extend(GENERATOR_Y.as_ref());
-        Secp256k1Point(PublicKey::from_slice(&v).unwrap())
+        generator_table().point()
+    }
+
+    /// Calculate `G * scalar` with the precomputed table of the generator.
+    pub fn mul_generator(scalar: &Secp256k1Scalar) -> Secp256k1Point {
+        generator_table().mul(scalar)
+    }
+
+    /// Return the point at infinity, the identity of the group.
+    pub fn identity() -> Secp256k1Point {
+        Secp256k1Point(None)
+    }
+
+    /// Whether the point is the point at infinity.
+    pub fn is_identity(&self) -> bool {
+        self.0.is_none()
+    }
+
+    /// Map arbitrary data to a curve point with unknown discrete logarithm.
+    ///
+    /// Uses try-and-increment: `SHA256(data || counter)` is taken as an x
+    /// coordinate with even y until it lands on the curve.
+    pub fn hash_to_curve(data: &[u8]) -> Secp256k1Point {
+        (0u32..)
+            .find_map(|counter| {
+                let mut hasher = Sha256::new();
+                hasher.update(data);
+                hasher.update(counter.to_be_bytes());
+                let mut v = vec![2_u8];
+                v.extend(hasher.finalize());
+                PublicKey::from_slice(&v).ok()
+            })
+            .map(|public_key| Secp256k1Point(Some(public_key)))
+            .unwrap()
+    }
+
+    /// Serialize the point in 33 bytes compressed form, the identity as 33 zero bytes.
+    pub fn serialize(&self) -> [u8; 33] {
+        match self.0 {
+            Some(public_key) => public_key.serialize(),
+            None => [0u8; 33],
+        }
+    }
+
+    /// Parse the 33 bytes compressed form produced by `serialize`.
+    pub fn from_slice(bytes: &[u8]) -> Option<Secp256k1Point> {
+        if bytes.len() == 33 && bytes.iter().all(|b| *b == 0) {
+            return Some(Secp256k1Point::identity());
+        }
+        PublicKey::from_slice(bytes)
+            .ok()
+            .map(|public_key| Secp256k1Point(Some(public_key)))
+    }
+
+    /// The wrapped `secp256k1::PublicKey`.
+    ///
+    /// Panics on the identity, which has no `PublicKey`.
+    pub fn as_public_key(&self) -> &PublicKey {
+        self.0.as_ref().expect("the identity is not a public key")
+    }
+
+    /// Whether the point has an even y coordinate, as BIP-340 requires of keys and nonces.
+    pub fn has_even_y(&self) -> bool {
+        self.serialize()[0] == 2
+    }
+
+    /// The 32 bytes x coordinate, the BIP-340 encoding of the point.
+    pub fn x_only(&self) -> [u8; 32] {
+        let mut x = [0u8; 32];
+        x.copy_from_slice(&self.serialize()[1..]);
+        x
+    }
+
+    /// Lift a BIP-340 x coordinate to the point with even y, if it is on the curve.
+    pub fn from_x_only(x: &[u8; 32]) -> Option<Secp256k1Point> {
+        let mut v = vec![2_u8];
+        v.extend(x.iter());
+        PublicKey::from_slice(&v)
+            .ok()
+            .map(|public_key| Secp256k1Point(Some(public_key)))
+    }
+
+    /// Calculate the multi-scalar multiplication `sum points[i] * scalars[i]`
+    /// with Pippenger's bucket method.
+    ///
+    /// `PublicKey` only adds points in affine coordinates, paying a field
+    /// inversion per addition, so the buckets are accumulated in projective
+    /// coordinates with `k256` and only the result is converted back. That
+    /// only pays off from `PIPPENGER_MIN_TERMS` terms, shorter sums multiply
+    /// term by term.
+    pub fn multiscalar_mul(
+        scalars: &[Secp256k1Scalar],
+        points: &[Secp256k1Point],
+    ) -> Secp256k1Point {
+        let terms: Vec<(&Secp256k1Scalar, &Secp256k1Point)> = scalars
+            .iter()
+            .zip(points.iter())
+            .filter(|(_, point)| !point.is_identity())
+            .collect();
+        if terms.len() < PIPPENGER_MIN_TERMS {
+            return terms
+                .into_iter()
+                .fold(Secp256k1Point::identity(), |sum, (scalar, point)| {
+                    sum + *point * *scalar
+                });
+        }
+        let terms: Vec<([u8; 32], ProjectivePoint)> = terms
+            .into_iter()
+            .map(|(scalar, point)| (scalar.serialize(), point.to_projective()))
+            .collect();
+        // about log2(n) - 2 bits per window balances bucket filling and summing
+        let log_n = (usize::BITS - 1 - terms.len().leading_zeros()) as usize;
+        let width = log_n.saturating_sub(2).clamp(2, 16);
+        let mut sum = ProjectivePoint::IDENTITY;
+        for window in (0..256_usize.div_ceil(width)).rev() {
+            for _ in 0..width {
+                sum = sum.double();
+            }
+            let mut buckets = vec![ProjectivePoint::IDENTITY; (1 << width) - 1];
+            for (scalar, point) in terms.iter() {
+                let digit = window_digit(scalar, window * width, width);
+                if digit != 0 {
+                    buckets[digit - 1] += point;
+                }
+            }
+            // adds bucket d exactly d times
+            let mut running = ProjectivePoint::IDENTITY;
+            for bucket in buckets.iter().rev() {
+                running += bucket;
+                sum += running;
+            }
+        }
+        Secp256k1Point::from_projective(&sum)
+    }
+
+    fn to_projective(self) -> ProjectivePoint {
+        match self.0 {
+            Some(public_key) => {
+                let encoded = EncodedPoint::from_bytes(public_key.serialize_uncompressed())
+                    .expect("a serialized public key is a valid encoding");
+                ProjectivePoint::from(
+                    AffinePoint::from_encoded_point(&encoded)
+                        .expect("a public key is on the curve"),
+                )
+            }
+            None => ProjectivePoint::IDENTITY,
+        }
+    }
+
+    fn from_projective(point: &ProjectivePoint) -> Secp256k1Point {
+        // the uncompressed encoding spares `PublicKey` a square root
+        let encoded = point.to_affine().to_encoded_point(false);
+        if encoded.is_identity() {
+            return Secp256k1Point::identity();
+        }
+        Secp256k1Point(Some(
+            PublicKey::from_slice(encoded.as_bytes()).expect("a k256 point is on the curve"),
+        ))
     }
 
     // fn random_point() -> Secp256k1Point {
@@ -30,137 +189,309 @@ impl Secp256k1Point {
     //     base_point.scalar_mul(&random_scalar)
     // }
 
-    fn add_point(&self, other: &PublicKey) -> Secp256k1Point {
-        Secp256k1Point(self.0.combine(other).unwrap())
+    fn add_point(&self, other: &Secp256k1Point) -> Secp256k1Point {
+        match (self.0, other.0) {
+            (None, _) => *other,
+            (_, None) => *self,
+            // combining fails exactly when the sum is the point at infinity
+            (Some(a), Some(b)) => Secp256k1Point(a.combine(&b).ok()),
+        }
     }
 
     fn scalar_mul(&self, other: &Secp256k1Scalar) -> Secp256k1Point {
-        let mut new_point = *self;
-        new_point
-            .0
-            .mul_assign(get_context(), &other.0[..])
-            .expect("Assignment expected");
-        new_point
+        match self.0 {
+            Some(mut public_key) if *other != Secp256k1Scalar::zero() => {
+                public_key
+                    .mul_assign(get_context(), &other.serialize())
+                    .expect("Assignment expected");
+                Secp256k1Point(Some(public_key))
+            }
+            _ => Secp256k1Point::identity(),
+        }
     }
 }
 
 impl Secp256k1Scalar {
-    ///
+    /// Sample a uniformly random non-zero scalar.
     pub fn new_random() -> Secp256k1Scalar {
-        // let rand_bytes = thread_rng().gen::<[u8; 32]>();
-        let mut rand_bytes = [0u8; 32];
-        thread_rng().fill(&mut rand_bytes[..]);
-        Secp256k1Scalar(SecretKey::from_slice(&rand_bytes[..]).unwrap())
+        Secp256k1Scalar::random(&mut thread_rng())
     }
 
-    ///
+    /// Sample a uniformly random non-zero scalar from the given generator.
+    pub fn random<R: Rng + ?Sized>(rng: &mut R) -> Secp256k1Scalar {
+        loop {
+            let mut rand_bytes = [0u8; 32];
+            rng.fill(&mut rand_bytes[..]);
+            if let Some(scalar) = Secp256k1Scalar::from_slice(&rand_bytes[..]) {
+                return scalar;
+            }
+        }
+    }
+
+    /// Return the additive identity `0`.
     pub fn zero() -> Secp256k1Scalar {
-        let zero_arr = [0u8; 32];
-        let zero = unsafe { std::mem::transmute::<[u8; 32], SecretKey>(zero_arr) };
-        Secp256k1Scalar(zero)
+        Secp256k1Scalar([0; 4])
     }
 
-    ///
+    /// Return the multiplicative identity `1`.
     pub fn one() -> Secp256k1Scalar {
-        Secp256k1Scalar::from_bigint(&BigInt::from(1))
+        Secp256k1Scalar([1, 0, 0, 0])
     }
-    fn to_bigint(&self) -> BigInt {
-        // Scalar is big endian in bitcoin secp256k1 impl
-        BigInt::from_bytes_be(Plus, &self.0[..])
+
+    /// Map a small integer, such as a share index, to a scalar. Every `u64`
+    /// is below the curve order, so it is the lowest limb as is.
+    pub fn from_u64(n: u64) -> Secp256k1Scalar {
+        Secp256k1Scalar([n, 0, 0, 0])
     }
 
-    ///
+    /// Return the order `n` of the secp256k1 group.
     pub fn curve_order() -> BigInt {
         BigInt::from_bytes_be(Plus, &CURVE_ORDER)
     }
 
     fn add_scalar(&self, other: &Secp256k1Scalar) -> Secp256k1Scalar {
-        let result_bigint = self.to_bigint() + other.to_bigint();
-        let result_bigint_mod = result_bigint.mod_floor(&Secp256k1Scalar::curve_order());
-        Secp256k1Scalar::from_bigint(&result_bigint_mod)
+        let (sum, carry) = add_limbs(&self.0, &other.0);
+        Secp256k1Scalar(reduce_once(&sum, carry))
     }
 
     fn sub_scalar(&self, other: &Secp256k1Scalar) -> Secp256k1Scalar {
-        let result_bigint = self.to_bigint() - other.to_bigint();
-        let result_bigint_mod = result_bigint.mod_floor(&Secp256k1Scalar::curve_order());
-        Secp256k1Scalar::from_bigint(&result_bigint_mod)
+        let (difference, borrow) = sub_limbs(&self.0, &other.0);
+        // add n back if the subtraction wrapped around
+        let mask = borrow.wrapping_neg();
+        let n = [
+            ORDER[0] & mask,
+            ORDER[1] & mask,
+            ORDER[2] & mask,
+            ORDER[3] & mask,
+        ];
+        Secp256k1Scalar(add_limbs(&difference, &n).0)
     }
 
     fn mul_scalar(&self, other: &Secp256k1Scalar) -> Secp256k1Scalar {
-        let result_bigint = self.to_bigint() * other.to_bigint();
-        let result_bigint_mod = result_bigint.mod_floor(&Secp256k1Scalar::curve_order());
-        Secp256k1Scalar::from_bigint(&result_bigint_mod)
+        // Scalars are kept in canonical form rather than the Montgomery
+        // domain, so a product costs a second Montgomery multiplication to
+        // cancel the R^-1. That is far cheaper than a point multiplication,
+        // while every point multiplication, hash and encoding reads the
+        // canonical bytes, which the Montgomery domain would make pay for a
+        // conversion out of it.
+        // a * b * R^-1, then * R^2 * R^-1 to leave the Montgomery domain
+        let product = montgomery_mul(&self.0, &other.0);
+        Secp256k1Scalar(montgomery_mul(&product, &R_SQUARED))
     }
 
+    /// Calculate the inverse of Scalar modulo the curve order.
     ///
+    /// Computes `self^(n - 2)` by Fermat's little theorem, with the same
+    /// sequence of operations for every scalar. Zero maps to zero.
     pub fn inv(&self) -> Secp256k1Scalar {
-        let element = self.to_bigint();
-        let modulus = Secp256k1Scalar::curve_order();
-        Secp256k1Scalar::from_bigint(&element.mod_inverse(&modulus).unwrap())
+        let exponent = sub_limbs(&ORDER, &[2, 0, 0, 0]).0;
+        // fixed 4 bits windows in the Montgomery domain, where 1 is R mod n
+        let mut powers = [montgomery_mul(&[1, 0, 0, 0], &R_SQUARED); 16];
+        powers[1] = montgomery_mul(&self.0, &R_SQUARED);
+        for i in 2..16 {
+            powers[i] = montgomery_mul(&powers[i - 1], &powers[1]);
+        }
+        let mut result = powers[0];
+        for window in (0..64).rev() {
+            for _ in 0..4 {
+                result = montgomery_mul(&result, &result);
+            }
+            // the exponent is public, indexing by its digits leaks nothing
+            let digit = (exponent[window / 16] >> (4 * (window % 16))) & 0x0f;
+            if digit != 0 {
+                result = montgomery_mul(&result, &powers[digit as usize]);
+            }
+        }
+        Secp256k1Scalar(montgomery_mul(&result, &[1, 0, 0, 0]))
     }
 
-    /// Calculate the inverse of Scalar, using the Euclid Extend Algorithm
+    /// Calculate the inverse of Scalar modulo the curve order, same as `inv`.
     pub fn inverse(&self) -> Secp256k1Scalar {
-        let num = self.to_bigint();
-        let order = Secp256k1Scalar::curve_order();
-        let (mut r, mut next_r, mut s, mut next_s, mut t, mut next_t) = (
-            order.clone(),
-            num.clone(),
-            BigInt::from(1),
-            BigInt::from(0),
-            BigInt::from(0),
-            BigInt::from(1),
-        );
-        let mut quotient;
-        let mut tmp;
-        while next_r > BigInt::from(0) {
-            quotient = r.clone() / next_r.clone();
-            tmp = next_r.clone();
-            next_r = r.clone() - next_r.clone() * quotient.clone();
-            r = tmp.clone();
-            tmp = next_s.clone();
-            next_s = s - next_s.clone() * quotient.clone();
-            s = tmp;
-            tmp = next_t.clone();
-            next_t = t - next_t * quotient;
-            t = tmp;
-        }
-        Secp256k1Scalar::from_bigint(&t)
+        self.inv()
     }
 
-    ///
+    /// Reduce the scalar modulo the curve order. Scalars are always reduced,
+    /// so this is a copy.
     pub fn mod_scalar(&self) -> Secp256k1Scalar {
-        let bigint_self = self.to_bigint();
-        let mod_bigint_self = bigint_self.mod_floor(&Secp256k1Scalar::curve_order());
-        // println!(
-        //     "bigint_self: {:?}, curve_order: {:?}, mod_bigint_self: {:?}",
-        //     bigint_self,
-        //     Secp256k1Scalar::curve_order(),
-        //     mod_bigint_self
-        // );
-        Secp256k1Scalar::from_bigint(&mod_bigint_self)
+        *self
     }
 
-    ///
+    /// Build a scalar from a non-negative `BigInt` smaller than the curve order.
     pub fn from_bigint(n: &BigInt) -> Secp256k1Scalar {
-        if *n == BigInt::from(0) {
-            Secp256k1Scalar::zero()
-        } else {
-            let (_sign, mut result_bytes) = n.to_bytes_be();
-            if result_bytes.len() < SECRET_KEY_SIZE {
-                let mut padding = vec![0; SECRET_KEY_SIZE - result_bytes.len()];
-                padding.extend(result_bytes.iter());
-                result_bytes = padding
-            }
-            // println!("secret_key: {:?}", result_bytes);
-            Secp256k1Scalar(SecretKey::from_slice(&result_bytes).unwrap())
+        let (sign, bytes) = n.to_bytes_be();
+        assert!(
+            sign != Minus && bytes.len() <= SECRET_KEY_SIZE,
+            "the integer is not below the curve order"
+        );
+        let mut padded = [0u8; 32];
+        padded[32 - bytes.len()..].copy_from_slice(&bytes);
+        let limbs = limbs_from_bytes(&padded);
+        assert!(
+            sub_limbs(&limbs, &ORDER).1 == 1,
+            "the integer is not below the curve order"
+        );
+        Secp256k1Scalar(limbs)
+    }
+
+    /// Serialize the scalar in 32 bytes big endian form.
+    pub fn serialize(&self) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        for (i, limb) in self.0.iter().enumerate() {
+            bytes[24 - 8 * i..32 - 8 * i].copy_from_slice(&limb.to_be_bytes());
         }
+        bytes
     }
-    ///
+
+    /// Parse 32 big endian bytes, rejecting zero and values not below the curve order.
+    pub fn from_slice(bytes: &[u8]) -> Option<Secp256k1Scalar> {
+        if bytes.len() != 32 {
+            return None;
+        }
+        let mut array = [0u8; 32];
+        array.copy_from_slice(bytes);
+        let limbs = limbs_from_bytes(&array);
+        if limbs == [0; 4] || sub_limbs(&limbs, &ORDER).1 == 0 {
+            return None;
+        }
+        Some(Secp256k1Scalar(limbs))
+    }
+
+    /// Hash arbitrary data to a scalar, `SHA256(data) mod n`.
+    pub fn hash_to_scalar(data: &[u8]) -> Secp256k1Scalar {
+        Secp256k1Scalar::from_bytes_mod_order(&Sha256::digest(data))
+    }
+
+    /// Interpret big endian bytes as an integer and reduce it modulo the curve order.
+    pub fn from_bytes_mod_order(bytes: &[u8]) -> Secp256k1Scalar {
+        // Horner's rule in base 2^256, from the most significant 32 bytes block
+        let head = match bytes.len() % 32 {
+            0 => 32.min(bytes.len()),
+            partial => partial,
+        };
+        let (first, rest) = bytes.split_at(head);
+        let mut result = reduce_block(first);
+        for block in rest.chunks(32) {
+            // result * 2^256 is the Montgomery product of result and R^2
+            let shifted = Secp256k1Scalar(montgomery_mul(&result.0, &R_SQUARED));
+            result = shifted + reduce_block(block);
+        }
+        result
+    }
+
+    /// Build a scalar from a big endian hex string.
     pub fn from_hex(hex: &[u8]) -> Secp256k1Scalar {
         Secp256k1Scalar::from_bigint(&BigInt::parse_bytes(hex, 16).unwrap())
     }
 }
+
+/// The curve order `n`, in little endian 64 bits limbs.
+const ORDER: [u64; 4] = [
+    0xbfd2_5e8c_d036_4141,
+    0xbaae_dce6_af48_a03b,
+    0xffff_ffff_ffff_fffe,
+    0xffff_ffff_ffff_ffff,
+];
+
+/// `-n^-1 mod 2^64`, the Montgomery reduction factor.
+const ORDER_INVERSE: u64 = 0x4b0d_ff66_5588_b13f;
+
+/// `R^2 mod n` with `R = 2^256`, maps into the Montgomery domain.
+const R_SQUARED: [u64; 4] = [
+    0x896c_f214_67d7_d140,
+    0x7414_96c2_0e7c_f878,
+    0xe697_f5e4_5bcd_07c6,
+    0x9d67_1cd5_81c6_9bc5,
+];
+
+/// Read 32 big endian bytes into little endian limbs.
+fn limbs_from_bytes(bytes: &[u8; 32]) -> [u64; 4] {
+    let mut limbs = [0u64; 4];
+    for (i, limb) in limbs.iter_mut().enumerate() {
+        let mut word = [0u8; 8];
+        word.copy_from_slice(&bytes[24 - 8 * i..32 - 8 * i]);
+        *limb = u64::from_be_bytes(word);
+    }
+    limbs
+}
+
+/// Reduce at most 32 big endian bytes modulo `n`. A single subtraction
+/// suffices as `2^256 < 2n`.
+fn reduce_block(block: &[u8]) -> Secp256k1Scalar {
+    let mut padded = [0u8; 32];
+    padded[32 - block.len()..].copy_from_slice(block);
+    Secp256k1Scalar(reduce_once(&limbs_from_bytes(&padded), 0))
+}
+
+/// `a + b` and the carry out.
+fn add_limbs(a: &[u64; 4], b: &[u64; 4]) -> ([u64; 4], u64) {
+    let mut sum = [0u64; 4];
+    let mut carry = 0u64;
+    for i in 0..4 {
+        let wide = a[i] as u128 + b[i] as u128 + carry as u128;
+        sum[i] = wide as u64;
+        carry = (wide >> 64) as u64;
+    }
+    (sum, carry)
+}
+
+/// `a - b` and the borrow out.
+fn sub_limbs(a: &[u64; 4], b: &[u64; 4]) -> ([u64; 4], u64) {
+    let mut difference = [0u64; 4];
+    let mut borrow = 0u64;
+    for i in 0..4 {
+        let wide = (a[i] as u128)
+            .wrapping_sub(b[i] as u128)
+            .wrapping_sub(borrow as u128);
+        difference[i] = wide as u64;
+        borrow = (wide >> 127) as u64;
+    }
+    (difference, borrow)
+}
+
+/// Reduce `carry * 2^256 + a`, known to be below `2n`, to below `n`,
+/// choosing between `a` and `a - n` with a mask rather than a branch.
+fn reduce_once(a: &[u64; 4], carry: u64) -> [u64; 4] {
+    let (difference, borrow) = sub_limbs(a, &ORDER);
+    // keep a - n unless it borrowed without a carry to cover it
+    let keep_a = (borrow & !carry).wrapping_neg();
+    let mut result = [0u64; 4];
+    for i in 0..4 {
+        result[i] = (a[i] & keep_a) | (difference[i] & !keep_a);
+    }
+    result
+}
+
+/// The Montgomery product `a * b * 2^-256 mod n` of two reduced operands,
+/// interleaving multiplication and reduction limb by limb (CIOS).
+fn montgomery_mul(a: &[u64; 4], b: &[u64; 4]) -> [u64; 4] {
+    let mut t = [0u64; 6];
+    for b_i in b.iter() {
+        let mut carry = 0u64;
+        for j in 0..4 {
+            let wide = t[j] as u128 + a[j] as u128 * *b_i as u128 + carry as u128;
+            t[j] = wide as u64;
+            carry = (wide >> 64) as u64;
+        }
+        let wide = t[4] as u128 + carry as u128;
+        t[4] = wide as u64;
+        t[5] = (wide >> 64) as u64;
+
+        // add m * n, making the lowest limb zero, and shift it out
+        let m = t[0].wrapping_mul(ORDER_INVERSE);
+        let wide = t[0] as u128 + m as u128 * ORDER[0] as u128;
+        let mut carry = (wide >> 64) as u64;
+        for j in 1..4 {
+            let wide = t[j] as u128 + m as u128 * ORDER[j] as u128 + carry as u128;
+            t[j - 1] = wide as u64;
+            carry = (wide >> 64) as u64;
+        }
+        let wide = t[4] as u128 + carry as u128;
+        t[3] = wide as u64;
+        t[4] = t[5] + (wide >> 64) as u64;
+    }
+    reduce_once(&[t[0], t[1], t[2], t[3]], t[4])
+}
+
 impl Add<Secp256k1Scalar> for Secp256k1Scalar {
     type Output = Secp256k1Scalar;
     fn add(self, other: Secp256k1Scalar) -> Self::Output {
@@ -179,10 +510,35 @@ impl Mul<Secp256k1Scalar> for Secp256k1Scalar {
         self.mul_scalar(&other)
     }
 }
+impl Neg for Secp256k1Scalar {
+    type Output = Secp256k1Scalar;
+    fn neg(self) -> Self::Output {
+        Secp256k1Scalar::zero().sub_scalar(&self)
+    }
+}
 impl Add<Secp256k1Point> for Secp256k1Point {
     type Output = Secp256k1Point;
     fn add(self, other: Secp256k1Point) -> Self::Output {
-        self.add_point(&other.0)
+        self.add_point(&other)
+    }
+}
+
+impl Sub<Secp256k1Point> for Secp256k1Point {
+    type Output = Secp256k1Point;
+    fn sub(self, other: Secp256k1Point) -> Self::Output {
+        self.add_point(&-other)
+    }
+}
+
+impl Neg for Secp256k1Point {
+    type Output = Secp256k1Point;
+    fn neg(self) -> Self::Output {
+        if self.is_identity() {
+            return self;
+        }
+        let mut v = self.serialize();
+        v[0] ^= 1;
+        Secp256k1Point::from_slice(&v).unwrap()
     }
 }
 
@@ -193,14 +549,109 @@ impl Mul<Secp256k1Scalar> for Secp256k1Point {
     }
 }
 
-static mut CONTEXT: Option<Secp256k1<VerifyOnly>> = None;
+/// A point with a table of its multiples, making multiplications of that
+/// fixed base several times faster than `Secp256k1Point * Secp256k1Scalar`.
 ///
+/// The scalar is cut into 64 digits of 4 bits and the table holds
+/// `d * 16^i * point` for every digit `d` and position `i`, so a
+/// multiplication is 64 additions and no doublings. Entries are selected in
+/// constant time, as the scalar is often secret. Building the table takes
+/// about a thousand additions, it is worth it for bases used many times, such
+/// as Pedersen's second generator `h`; the table of `G` is built on first use.
+#[derive(Clone, Debug)]
+pub struct PrecomputedPoint {
+    point: Secp256k1Point,
+    table: Vec<[AffinePoint; 16]>,
+}
+
+impl PrecomputedPoint {
+    /// Precompute the multiples of `point`.
+    pub fn new(point: Secp256k1Point) -> PrecomputedPoint {
+        if point.is_identity() {
+            return PrecomputedPoint {
+                point,
+                table: vec![[AffinePoint::IDENTITY; 16]; 64],
+            };
+        }
+        // the non-zero multiples, as normalizing the identity would invert zero
+        let mut base = point.to_projective();
+        let mut multiples = Vec::with_capacity(64 * 15);
+        for _ in 0..64 {
+            let mut multiple = base;
+            for _ in 1..16 {
+                multiples.push(multiple);
+                multiple += base;
+            }
+            // 16 * 16^i * point
+            base = multiple;
+        }
+        let mut affine = vec![AffinePoint::IDENTITY; multiples.len()];
+        ProjectivePoint::batch_normalize(&multiples, &mut affine);
+        let table = affine
+            .chunks(15)
+            .map(|row| {
+                let mut entries = [AffinePoint::IDENTITY; 16];
+                entries[1..].copy_from_slice(row);
+                entries
+            })
+            .collect();
+        PrecomputedPoint { point, table }
+    }
+
+    /// The base point.
+    pub fn point(&self) -> Secp256k1Point {
+        self.point
+    }
+
+    /// Calculate `point * scalar`.
+    pub fn mul(&self, scalar: &Secp256k1Scalar) -> Secp256k1Point {
+        let bytes = scalar.serialize();
+        let mut sum = ProjectivePoint::IDENTITY;
+        for (i, row) in self.table.iter().enumerate() {
+            let digit = (bytes[31 - i / 2] >> (4 * (i % 2))) & 0x0f;
+            let mut entry = AffinePoint::IDENTITY;
+            for (d, candidate) in row.iter().enumerate() {
+                entry.conditional_assign(candidate, (d as u8).ct_eq(&digit));
+            }
+            sum += entry;
+        }
+        Secp256k1Point::from_projective(&sum)
+    }
+}
+
+impl Mul<Secp256k1Scalar> for &PrecomputedPoint {
+    type Output = Secp256k1Point;
+    fn mul(self, other: Secp256k1Scalar) -> Self::Output {
+        PrecomputedPoint::mul(self, &other)
+    }
+}
+
+/// The table of the generator, built on first use.
+fn generator_table() -> &'static PrecomputedPoint {
+    static TABLE: OnceLock<PrecomputedPoint> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        let mut v = vec![4_u8];
+        v.extend(GENERATOR_X.as_ref());
+        v.extend(GENERATOR_Y.as_ref());
+        PrecomputedPoint::new(Secp256k1Point(Some(PublicKey::from_slice(&v).unwrap())))
+    })
+}
+
+/// The number of terms from which the bucket method beats multiplying term by term.
+const PIPPENGER_MIN_TERMS: usize = 16;
+
+/// The `width` bits of the big endian scalar starting at bit `start`, counted from the least significant.
+fn window_digit(scalar: &[u8; 32], start: usize, width: usize) -> usize {
+    (start..(start + width).min(256))
+        .map(|bit| ((scalar[31 - bit / 8] >> (bit % 8)) & 1) as usize)
+        .enumerate()
+        .fold(0, |digit, (i, bit)| digit | (bit << i))
+}
+
+/// Return the shared verification context used for point arithmetic.
 pub fn get_context() -> &'static Secp256k1<VerifyOnly> {
-    static INIT_CONTEXT: Once = Once::new();
-    INIT_CONTEXT.call_once(|| unsafe {
-        CONTEXT = Some(Secp256k1::verification_only());
-    });
-    unsafe { CONTEXT.as_ref().unwrap() }
+    static CONTEXT: OnceLock<Secp256k1<VerifyOnly>> = OnceLock::new();
+    CONTEXT.get_or_init(Secp256k1::verification_only)
 }
 
 #[cfg(test)]
@@ -210,8 +661,138 @@ mod tests {
     #[test]
     fn test_bigint_to_scalar() {
         let random_scalar = Secp256k1Scalar::new_random();
-        let bigint = random_scalar.to_bigint();
+        let bigint = BigInt::from_bytes_be(Plus, &random_scalar.serialize());
         let scalar2 = Secp256k1Scalar::from_bigint(&bigint);
         assert_eq!(random_scalar, scalar2);
     }
+
+    #[test]
+    fn test_from_u64_matches_bigint() {
+        for n in [0, 1, 42, u64::MAX] {
+            assert_eq!(
+                Secp256k1Scalar::from_u64(n),
+                Secp256k1Scalar::from_bigint(&BigInt::from(n))
+            );
+        }
+    }
+
+    #[test]
+    fn test_arithmetic_matches_bigint() {
+        let order = Secp256k1Scalar::curve_order();
+        let to_bigint = |scalar: &Secp256k1Scalar| BigInt::from_bytes_be(Plus, &scalar.serialize());
+        let minus_one = Secp256k1Scalar::from_bigint(&(&order - 1));
+        let mut scalars = vec![
+            Secp256k1Scalar::zero(),
+            Secp256k1Scalar::one(),
+            minus_one,
+            Secp256k1Scalar::from_bigint(&(&order >> 1)),
+        ];
+        scalars.extend((0..8).map(|_| Secp256k1Scalar::new_random()));
+        for a in scalars.iter() {
+            for b in scalars.iter() {
+                let (x, y) = (to_bigint(a), to_bigint(b));
+                let reduce =
+                    |n: BigInt| Secp256k1Scalar::from_bigint(&(((n % &order) + &order) % &order));
+                assert_eq!(*a + *b, reduce(&x + &y));
+                assert_eq!(*a - *b, reduce(&x - &y));
+                assert_eq!(*a * *b, reduce(&x * &y));
+            }
+        }
+        assert_eq!(-Secp256k1Scalar::one(), minus_one);
+        assert_eq!(-Secp256k1Scalar::zero(), Secp256k1Scalar::zero());
+        assert_eq!(Secp256k1Scalar::zero().inverse(), Secp256k1Scalar::zero());
+        assert_eq!(minus_one.inverse(), minus_one);
+
+        // reduction of wide and unaligned inputs
+        let mut bytes = [0xffu8; 77];
+        bytes[3] = 0x42;
+        let expected = BigInt::from_bytes_be(Plus, &bytes) % &order;
+        assert_eq!(
+            Secp256k1Scalar::from_bytes_mod_order(&bytes),
+            Secp256k1Scalar::from_bigint(&expected)
+        );
+        assert_eq!(
+            Secp256k1Scalar::from_bytes_mod_order(&[]),
+            Secp256k1Scalar::zero()
+        );
+        assert_eq!(
+            Secp256k1Scalar::from_bytes_mod_order(&CURVE_ORDER),
+            Secp256k1Scalar::zero()
+        );
+        assert_eq!(Secp256k1Scalar::from_slice(&CURVE_ORDER), None);
+        assert_eq!(Secp256k1Scalar::from_slice(&[0u8; 32]), None);
+    }
+
+    #[test]
+    fn test_inverse() {
+        for _ in 0..16 {
+            let scalar = Secp256k1Scalar::new_random();
+            assert_eq!(scalar * scalar.inverse(), Secp256k1Scalar::one());
+        }
+    }
+
+    #[test]
+    fn test_precomputed_point() {
+        let generator = Secp256k1Point::generator();
+        let base = generator * Secp256k1Scalar::new_random();
+        let precomputed = PrecomputedPoint::new(base);
+        assert_eq!(precomputed.point(), base);
+        for _ in 0..10 {
+            let scalar = Secp256k1Scalar::new_random();
+            assert_eq!(&precomputed * scalar, base * scalar);
+            assert_eq!(Secp256k1Point::mul_generator(&scalar), generator * scalar);
+        }
+        let minus_one = -Secp256k1Scalar::one();
+        assert_eq!(&precomputed * minus_one, -base);
+        assert_eq!(
+            &precomputed * Secp256k1Scalar::zero(),
+            Secp256k1Point::identity()
+        );
+        let identity = PrecomputedPoint::new(Secp256k1Point::identity());
+        assert_eq!(&identity * minus_one, Secp256k1Point::identity());
+    }
+
+    #[test]
+    fn test_multiscalar_mul() {
+        let generator = Secp256k1Point::generator();
+        for n in [0, 1, 2, 5, 16, 40] {
+            let scalars: Vec<Secp256k1Scalar> =
+                (0..n).map(|_| Secp256k1Scalar::new_random()).collect();
+            let mut points: Vec<Secp256k1Point> = (0..n)
+                .map(|_| generator * Secp256k1Scalar::new_random())
+                .collect();
+            if n > 1 {
+                points[1] = Secp256k1Point::identity();
+            }
+            let expected = scalars
+                .iter()
+                .zip(points.iter())
+                .fold(Secp256k1Point::identity(), |sum, (s, p)| sum + *p * *s);
+            assert_eq!(Secp256k1Point::multiscalar_mul(&scalars, &points), expected);
+        }
+        // cancelling terms, above and below the bucket method cutoff
+        for n in [2, 32] {
+            let mut scalars = vec![Secp256k1Scalar::one(); n / 2];
+            scalars.extend(vec![-Secp256k1Scalar::one(); n / 2]);
+            scalars.push(Secp256k1Scalar::zero());
+            assert_eq!(
+                Secp256k1Point::multiscalar_mul(&scalars, &vec![generator; n + 1]),
+                Secp256k1Point::identity()
+            );
+        }
+    }
+
+    #[test]
+    fn test_identity_arithmetic() {
+        let generator = Secp256k1Point::generator();
+        let identity = Secp256k1Point::identity();
+        assert_eq!(generator - generator, identity);
+        assert_eq!(generator + identity, generator);
+        assert_eq!(generator * Secp256k1Scalar::zero(), identity);
+        assert_eq!(-identity, identity);
+        assert_eq!(
+            Secp256k1Point::from_slice(&identity.serialize()),
+            Some(identity)
+        );
+    }
 }
@@ -1,7 +1,7 @@
+use crate::errors::{check_share_indices, VssError};
+use crate::field::{self, BigIntField, PrimeField};
 pub use num_bigint;
-use num_bigint::{BigInt, RandBigInt};
-use num_traits::{One, Zero};
-use rand;
+use num_bigint::BigInt;
 /// The `ShamirSecretSharing` stores threshold, share_amount and the prime of finite field.
 #[derive(Clone, Debug)]
 pub struct ShamirSecretSharing {
@@ -22,125 +22,130 @@ impl ShamirSecretSharing {
         self.evaluate_polynomial(polynomial)
     }
 
+    /// The prime field of the shares.
+    pub fn field(&self) -> BigIntField {
+        BigIntField {
+            prime: self.prime.clone(),
+        }
+    }
+
     fn sample_polynomial(&self, secret: BigInt) -> Vec<BigInt> {
-        let mut coefficients: Vec<BigInt> = vec![secret];
-        let mut rng = rand::thread_rng();
-        let low = BigInt::from(0);
-        let high = &self.prime - BigInt::from(1);
-        let random_coefficients: Vec<BigInt> = (0..(self.threshold - 1))
-            .map(|_| rng.gen_bigint_range(&low, &high))
-            .collect();
-        coefficients.extend(random_coefficients);
-        coefficients
+        let field = self.field();
+        field::sample_polynomial(
+            &field,
+            &field.normalize(&secret),
+            self.threshold,
+            &mut rand::thread_rng(),
+        )
     }
 
     fn evaluate_polynomial(&self, polynomial: Vec<BigInt>) -> Vec<(usize, BigInt)> {
+        let field = self.field();
         (1..=self.share_amount)
-            .map(|x| (x, self.mod_evaluate_at(&polynomial, x)))
+            .map(|x| {
+                let x_field = field.element(x as u64);
+                (x, field::evaluate_polynomial(&field, &polynomial, &x_field))
+            })
             .collect()
     }
 
-    fn mod_evaluate_at(&self, polynomial: &[BigInt], x: usize) -> BigInt {
-        let x_bigint = BigInt::from(x);
-        polynomial.iter().rev().fold(Zero::zero(), |sum, item| {
-            (&x_bigint * sum + item) % &self.prime
-        })
-    }
-
-    /// Recover the secret by the shares.
+    /// Recover the secret by at least threshold shares.
+    ///
+    /// Panics if the shares are invalid or inconsistent, see `try_recover`.
     pub fn recover(&self, shares: &[(usize, BigInt)]) -> BigInt {
-        assert!(shares.len() == self.threshold, "wrong shares number");
-        let (xs, ys): (Vec<usize>, Vec<BigInt>) = shares.iter().cloned().unzip();
-        let result = self.lagrange_interpolation(Zero::zero(), xs, ys);
-        if result < Zero::zero() {
-            result + &self.prime
-        } else {
-            result
-        }
+        self.try_recover(shares)
+            .unwrap_or_else(|e| panic!("wrong shares: {}", e))
     }
 
-    fn lagrange_interpolation(&self, x: BigInt, xs: Vec<usize>, ys: Vec<BigInt>) -> BigInt {
-        let len = xs.len();
-        // println!("x: {}, xs: {:?}, ys: {:?}", x, xs, ys);
-        let xs_bigint: Vec<BigInt> = xs.iter().map(|x| BigInt::from(*x as i64)).collect();
-        // println!("sx_bigint: {:?}", xs_bigint);
-        (0..len).fold(Zero::zero(), |sum, item| {
-            let numerator = (0..len).fold(One::one(), |product: BigInt, i| {
-                if i == item {
-                    product
-                } else {
-                    product * (&x - &xs_bigint[i]) % &self.prime
-                }
+    /// Recover the secret by at least threshold shares.
+    ///
+    /// The secret is interpolated from the first threshold shares, every extra
+    /// share is then checked against that polynomial and the indices of those
+    /// which disagree are reported as `VssError::InconsistentShares`, which
+    /// does not necessarily name the corrupt shares; `recover_robust` locates
+    /// and skips them.
+    pub fn try_recover(&self, shares: &[(usize, BigInt)]) -> Result<BigInt, VssError> {
+        if self.threshold == 0 || shares.len() < self.threshold {
+            return Err(VssError::TooFewShares {
+                required: self.threshold,
+                provided: shares.len(),
             });
-            let denominator = (0..len).fold(One::one(), |product: BigInt, i| {
-                if i == item {
-                    product
-                } else {
-                    product * (&xs_bigint[item] - &xs_bigint[i]) % &self.prime
-                }
-            });
-            // println!(
-            // "numerator: {}, donominator: {}, y: {}",
-            // numerator, denominator, &ys[item]
-            // );
-            (sum + numerator * self.mod_reverse(denominator) * &ys[item]) % &self.prime
-        })
+        }
+        check_share_indices(shares)?;
+        let field = self.field();
+        let (xs, ys): (Vec<usize>, Vec<BigInt>) = shares[..self.threshold]
+            .iter()
+            .map(|(x, y)| (*x, field.normalize(y)))
+            .unzip();
+        let inconsistent: Vec<usize> = shares[self.threshold..]
+            .iter()
+            .filter(|(x, y)| {
+                let expected =
+                    field::lagrange_interpolation(&field, &field.element(*x as u64), &xs, &ys);
+                expected != field.normalize(y)
+            })
+            .map(|(x, _)| *x)
+            .collect();
+        if !inconsistent.is_empty() {
+            return Err(VssError::InconsistentShares(inconsistent));
+        }
+        Ok(field::lagrange_interpolation(
+            &field,
+            &field.zero(),
+            &xs,
+            &ys,
+        ))
     }
 
-    fn mod_reverse(&self, num: BigInt) -> BigInt {
-        let num1 = if num < Zero::zero() {
-            num + &self.prime
-        } else {
-            num
-        };
-        let (_gcd, _, inv) = self.extend_euclid_algo(num1);
-        // println!("inv:{}", inv);
-        inv
+    /// Recover the secret from shares of which up to `(n - threshold) / 2` may
+    /// be corrupt, returning it with the indices of the corrupt shares.
+    ///
+    /// Panics if there are too many corrupt shares, see `try_recover_robust`.
+    pub fn recover_robust(&self, shares: &[(usize, BigInt)]) -> (BigInt, Vec<usize>) {
+        self.try_recover_robust(shares)
+            .unwrap_or_else(|e| panic!("wrong shares: {}", e))
     }
 
-    /**
-     * https://en.wikipedia.org/wiki/Extended_Euclidean_algorithm
-     *
-     * a*s + b*t = gcd(a,b) a > b
-     * r_0 = a*s_0 + b*t_0    s_0 = 1    t_0 = 0
-     * r_1 = a*s_1 + b*t_1    s_1 = 0    t_1 = 1
-     * r_2 = r_0 - r_1*q_1
-     *     = a(s_0 - s_1*q_1) + b(t_0 - t_1*q_1)   s_2 = s_0 - s_1*q_1     t_2 = t_0 - t_1*q_1
-     * ...
-     * stop when r_k = 0
-     */
-    fn extend_euclid_algo(&self, num: BigInt) -> (BigInt, BigInt, BigInt) {
-        let (mut r, mut next_r, mut s, mut next_s, mut t, mut next_t) = (
-            self.prime.clone(),
-            num.clone(),
-            BigInt::from(1),
-            BigInt::from(0),
-            BigInt::from(0),
-            BigInt::from(1),
-        );
-        let mut quotient;
-        let mut tmp;
-        while next_r > Zero::zero() {
-            quotient = r.clone() / next_r.clone();
-            tmp = next_r.clone();
-            next_r = r.clone() - next_r.clone() * quotient.clone();
-            r = tmp.clone();
-            tmp = next_s.clone();
-            next_s = s - next_s.clone() * quotient.clone();
-            s = tmp;
-            tmp = next_t.clone();
-            next_t = t - next_t * quotient;
-            t = tmp;
+    /// Recover the secret from shares of which up to `(n - threshold) / 2` may
+    /// be corrupt, returning it with the indices of the corrupt shares.
+    ///
+    /// The polynomial is decoded with the Berlekamp-Welch algorithm, and fails
+    /// with `VssError::TooManyCorruptShares` if no polynomial of degree below
+    /// threshold agrees with all but that many shares.
+    pub fn try_recover_robust(
+        &self,
+        shares: &[(usize, BigInt)],
+    ) -> Result<(BigInt, Vec<usize>), VssError> {
+        if self.threshold == 0 || shares.len() < self.threshold {
+            return Err(VssError::TooFewShares {
+                required: self.threshold,
+                provided: shares.len(),
+            });
         }
-        // println!(
-        // "{} * {} + {} * {} = {} mod {}",
-        // num, t, &self.prime, s, r, &self.prime
-        // );
-        (r, s, t)
+        check_share_indices(shares)?;
+        let field = self.field();
+        let (xs, ys): (Vec<usize>, Vec<BigInt>) =
+            shares.iter().map(|(x, y)| (*x, field.normalize(y))).unzip();
+        let correctable = (shares.len() - self.threshold) / 2;
+        let polynomial = field::berlekamp_welch(&field, &xs, &ys, self.threshold)
+            .ok_or(VssError::TooManyCorruptShares { correctable })?;
+        let corrupt: Vec<usize> = xs
+            .iter()
+            .zip(ys.iter())
+            .filter(|(x, y)| {
+                field::evaluate_polynomial(&field, &polynomial, &field.element(**x as u64)) != **y
+            })
+            .map(|(x, _)| *x)
+            .collect();
+        if corrupt.len() > correctable {
+            return Err(VssError::TooManyCorruptShares { correctable });
+        }
+        Ok((polynomial[0].clone(), corrupt))
     }
 }
 
 #[cfg(test)]
+#[allow(clippy::unnecessary_cast)]
 mod tests {
     use super::*;
     #[test]
@@ -202,4 +207,80 @@ mod tests {
         let sig = secp.sign(&message, &secret);
         assert!(secp.verify(&message, &sig, &public).is_ok());
     }
+
+    #[test]
+    fn test_recover_from_superset() {
+        let sss = ShamirSecretSharing {
+            threshold: 3,
+            share_amount: 6,
+            prime: BigInt::from(1613),
+        };
+        let shares = sss.evaluate_polynomial(vec![
+            BigInt::from(1234),
+            BigInt::from(166),
+            BigInt::from(94),
+        ]);
+        assert_eq!(sss.recover(&shares), BigInt::from(1234));
+        assert_eq!(sss.recover(&shares[2..]), BigInt::from(1234));
+    }
+
+    #[test]
+    fn test_recover_reports_inconsistent_shares() {
+        let sss = ShamirSecretSharing {
+            threshold: 3,
+            share_amount: 6,
+            prime: BigInt::from(1613),
+        };
+        let mut shares = sss.evaluate_polynomial(vec![
+            BigInt::from(1234),
+            BigInt::from(166),
+            BigInt::from(94),
+        ]);
+        let honest = shares.clone();
+        shares[5].1 = BigInt::from(1);
+        assert_eq!(
+            sss.try_recover(&shares).unwrap_err(),
+            VssError::InconsistentShares(vec![6])
+        );
+        // a corrupt share among the first threshold makes the extras disagree
+        shares[5] = honest[5].clone();
+        shares[0].1 = BigInt::from(1);
+        assert!(sss.try_recover(&shares).is_err());
+        assert_eq!(sss.recover_robust(&shares), (BigInt::from(1234), vec![1]));
+        assert_eq!(
+            sss.try_recover(&shares[0..2]).unwrap_err(),
+            VssError::TooFewShares {
+                required: 3,
+                provided: 2
+            }
+        );
+    }
+
+    #[test]
+    fn test_recover_robust_identifies_corrupt_shares() {
+        let sss = ShamirSecretSharing {
+            threshold: 3,
+            share_amount: 9,
+            prime: BigInt::parse_bytes(
+                b"fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f",
+                16,
+            )
+            .unwrap(),
+        };
+        let secret = BigInt::parse_bytes(b"ffffffffffffffffffffffffffffffffffffff", 16).unwrap();
+        let mut shares = sss.split(secret.clone());
+        assert_eq!(sss.recover_robust(&shares), (secret.clone(), vec![]));
+        shares[0].1 = BigInt::from(1);
+        shares[4].1 += 1;
+        shares[8].1 = -shares[8].1.clone();
+        assert_eq!(sss.recover_robust(&shares), (secret.clone(), vec![1, 5, 9]));
+        assert_eq!(
+            sss.recover_robust(&shares[..7]),
+            (secret.clone(), vec![1, 5])
+        );
+        assert_eq!(
+            sss.try_recover_robust(&shares[..6]).unwrap_err(),
+            VssError::TooManyCorruptShares { correctable: 1 }
+        );
+    }
 }
@@ -0,0 +1,263 @@
+use crate::curve::Secp256k1;
+use crate::errors::{check_share_indices, VssError};
+use crate::feldman_vss::VerifiableSecretSharing;
+use crate::ida::InformationDispersal;
+use crate::secp256k1_helper::{Secp256k1Point, Secp256k1Scalar};
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{ChaCha20Poly1305, Nonce};
+use rand::{thread_rng, Rng};
+use sha2::{Digest, Sha256};
+
+/// The domain separator of the key derivation.
+const KEY_DERIVATION_LABEL: &[u8] = b"verifiable_secret_sharing/ssms/key";
+
+/// A participant's share of the data: a fragment of the ciphertext and a
+/// Feldman share of the encryption key.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SsmsShare {
+    /// the index of the participant.
+    pub index: usize,
+    /// the participant's fragment of the ciphertext.
+    pub fragment: Vec<u8>,
+    /// the participant's share of the key scalar.
+    pub key_share: Secp256k1Scalar,
+}
+
+/// The `SecretSharingMadeShort` implements Krawczyk's hybrid secret sharing:
+/// the data is encrypted with ChaCha20-Poly1305 under a fresh key, the
+/// ciphertext is dispersed with Rabin's IDA so each share holds about
+/// `|data| / threshold` bytes of it, and only the key is shared with
+/// Feldman's VSS over secp256k1.
+#[derive(Clone, Debug)]
+pub struct SecretSharingMadeShort {
+    /// the threshold of shares to recover the data.
+    pub threshold: usize,
+    /// the total number of shares to generate from the data.
+    pub share_amount: usize,
+}
+
+impl SecretSharingMadeShort {
+    /// Split the data to shares and the commitments of the key sharing.
+    ///
+    /// Panics if the threshold or share amount is invalid, see `try_split`.
+    pub fn split(&self, data: &[u8]) -> (Vec<SsmsShare>, Vec<Secp256k1Point>) {
+        self.try_split(data).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Split the data to shares and the commitments of the key sharing,
+    /// failing on an invalid threshold or more than 255 shares.
+    pub fn try_split(
+        &self,
+        data: &[u8],
+    ) -> Result<(Vec<SsmsShare>, Vec<Secp256k1Point>), VssError> {
+        self.try_split_with_rng(data, &mut thread_rng())
+    }
+
+    /// Split the data, sampling the key and its sharing from `rng`.
+    pub fn try_split_with_rng<R: Rng + ?Sized>(
+        &self,
+        data: &[u8],
+        rng: &mut R,
+    ) -> Result<(Vec<SsmsShare>, Vec<Secp256k1Point>), VssError> {
+        let key = Secp256k1Scalar::random(rng);
+        let (key_shares, commitments) = self.feldman().try_split_with_rng(&key, rng)?;
+        let ciphertext = cipher(&key)
+            .encrypt(&Nonce::default(), data)
+            .expect("encryption in memory cannot fail");
+        let fragments = self.dispersal().try_disperse(&ciphertext)?;
+        let shares = key_shares
+            .into_iter()
+            .zip(fragments)
+            .map(|((index, key_share), (_, fragment))| SsmsShare {
+                index,
+                fragment,
+                key_share,
+            })
+            .collect();
+        Ok((shares, commitments))
+    }
+
+    /// Verify the key share of a participant against the commitments.
+    pub fn verify(share: &SsmsShare, commitments: &[Secp256k1Point]) -> bool {
+        VerifiableSecretSharing::verify((share.index, share.key_share), commitments)
+    }
+
+    /// Recover the data by at least threshold shares.
+    ///
+    /// Panics if the shares are invalid or the data does not authenticate,
+    /// see `try_recover`.
+    pub fn recover(&self, shares: &[SsmsShare]) -> Vec<u8> {
+        self.try_recover(shares).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Recover the data by at least threshold shares.
+    ///
+    /// The key and the ciphertext are rebuilt as `VerifiableSecretSharing::try_recover`
+    /// and the dispersal do, and decrypting the ciphertext authenticates the
+    /// result: any tampering with fragments or key shares that survives those
+    /// consistency checks fails with `VssError::AuthenticationFailed`, or with
+    /// `VssError::MalformedEncoding` if it breaks the framing of the ciphertext.
+    pub fn try_recover(&self, shares: &[SsmsShare]) -> Result<Vec<u8>, VssError> {
+        if self.threshold == 0 || shares.len() < self.threshold {
+            return Err(VssError::TooFewShares {
+                required: self.threshold,
+                provided: shares.len(),
+            });
+        }
+        let indexed: Vec<(usize, ())> = shares.iter().map(|share| (share.index, ())).collect();
+        check_share_indices(&indexed)?;
+        if let Some(share) = shares.iter().find(|share| share.index > 255) {
+            return Err(VssError::InconsistentShares(vec![share.index]));
+        }
+        let key_shares: Vec<(usize, Secp256k1Scalar)> = shares
+            .iter()
+            .map(|share| (share.index, share.key_share))
+            .collect();
+        let key = self.feldman().try_recover(&key_shares)?;
+        let fragments: Vec<(u8, Vec<u8>)> = shares
+            .iter()
+            .map(|share| (share.index as u8, share.fragment.clone()))
+            .collect();
+        let ciphertext = self.dispersal().try_reconstruct(&fragments)?;
+        cipher(&key)
+            .decrypt(&Nonce::default(), ciphertext.as_slice())
+            .map_err(|_| VssError::AuthenticationFailed)
+    }
+
+    /// Recover the data by at least threshold shares, checking every key
+    /// share against the commitments first.
+    ///
+    /// Shares whose key share does not verify are dropped and their indices
+    /// returned with the data, failing with `VssError::InvalidShares` if fewer
+    /// than threshold shares remain. The fragments are not committed to, so a
+    /// tampered fragment still fails as in `try_recover`, without blame.
+    pub fn recover_verified(
+        &self,
+        shares: &[SsmsShare],
+        commitments: &[Secp256k1Point],
+    ) -> Result<(Vec<u8>, Vec<usize>), VssError> {
+        let key_shares: Vec<(usize, Secp256k1Scalar)> = shares
+            .iter()
+            .map(|share| (share.index, share.key_share))
+            .collect();
+        let (_, invalid) = self.feldman().recover_verified(&key_shares, commitments)?;
+        let valid: Vec<SsmsShare> = shares
+            .iter()
+            .filter(|share| !invalid.contains(&share.index))
+            .cloned()
+            .collect();
+        Ok((self.try_recover(&valid)?, invalid))
+    }
+
+    fn feldman(&self) -> VerifiableSecretSharing<Secp256k1> {
+        VerifiableSecretSharing {
+            threshold: self.threshold,
+            share_amount: self.share_amount,
+            curve: Secp256k1,
+        }
+    }
+
+    fn dispersal(&self) -> InformationDispersal {
+        InformationDispersal {
+            threshold: self.threshold,
+            fragment_amount: self.share_amount,
+        }
+    }
+}
+
+/// The cipher keyed by `SHA256(label || key)`. Every key encrypts a single
+/// message, so the all-zero nonce is never reused.
+fn cipher(key: &Secp256k1Scalar) -> ChaCha20Poly1305 {
+    let mut hasher = Sha256::new();
+    hasher.update(KEY_DERIVATION_LABEL);
+    hasher.update(key.serialize());
+    ChaCha20Poly1305::new_from_slice(&hasher.finalize()).expect("SHA256 outputs 32 bytes keys")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ssms_3_of_5_works() {
+        let ssms = SecretSharingMadeShort {
+            threshold: 3,
+            share_amount: 5,
+        };
+        let mut data = vec![0u8; 30_000];
+        thread_rng().fill(&mut data[..]);
+        let (shares, commitments) = ssms.split(&data);
+        for share in shares.iter() {
+            assert!(SecretSharingMadeShort::verify(share, &commitments));
+            // the ciphertext, its tag and the length prefix, split three ways
+            assert_eq!(share.fragment.len(), (data.len() + 16 + 8).div_ceil(3));
+        }
+        assert_eq!(ssms.recover(&shares[..3]), data);
+        assert_eq!(ssms.recover(&shares[2..]), data);
+        assert_eq!(ssms.recover(&shares[1..4]), data);
+    }
+
+    #[test]
+    fn test_ssms_detects_tampering() {
+        let ssms = SecretSharingMadeShort {
+            threshold: 2,
+            share_amount: 3,
+        };
+        let (shares, commitments) = ssms.split(b"the backup");
+
+        let mut forged = shares.clone();
+        forged[0].key_share = forged[0].key_share + Secp256k1Scalar::one();
+        assert!(!SecretSharingMadeShort::verify(&forged[0], &commitments));
+        assert_eq!(
+            ssms.try_recover(&forged[..2]),
+            Err(VssError::AuthenticationFailed)
+        );
+
+        let mut forged = shares.clone();
+        forged[1].fragment[5] ^= 1;
+        assert_eq!(
+            ssms.try_recover(&forged[..2]),
+            Err(VssError::AuthenticationFailed)
+        );
+        // the third fragment exposes the tampering but not the tampered share
+        assert!(matches!(
+            ssms.try_recover(&forged),
+            Err(VssError::InconsistentShares(_))
+        ));
+        assert_eq!(
+            ssms.try_recover(&shares[..1]),
+            Err(VssError::TooFewShares {
+                required: 2,
+                provided: 1
+            })
+        );
+    }
+
+    #[test]
+    fn test_ssms_recover_verified_drops_forged_key_shares() {
+        let ssms = SecretSharingMadeShort {
+            threshold: 2,
+            share_amount: 4,
+        };
+        let (shares, commitments) = ssms.split(b"the backup");
+        let mut forged = shares.clone();
+        forged[0].key_share = forged[0].key_share + Secp256k1Scalar::one();
+        assert_eq!(
+            ssms.recover_verified(&forged[..3], &commitments),
+            Ok((b"the backup".to_vec(), vec![1]))
+        );
+        forged[1].key_share = Secp256k1Scalar::new_random();
+        forged[2].key_share = Secp256k1Scalar::new_random();
+        assert_eq!(
+            ssms.recover_verified(&forged[..3], &commitments),
+            Err(VssError::InvalidShares(vec![1, 2, 3]))
+        );
+        assert_eq!(
+            ssms.recover_verified(&shares, &commitments[..1]),
+            Err(VssError::DegreeMismatch {
+                expected: 2,
+                actual: 1
+            })
+        );
+    }
+}
@@ -0,0 +1,153 @@
+use crate::errors::VssError;
+use crate::feldman_vss::VerifiableSecretSharing;
+use bls12_381::hash_to_curve::{ExpandMsgXmd, HashToCurve};
+use bls12_381::{pairing, G1Affine, G1Projective, G2Affine, G2Projective, Scalar};
+use sha2::Sha256;
+
+/// The ciphersuite of the basic BLS signature scheme with public keys in G1.
+const SIGNATURE_DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_NUL_";
+
+/// A shareholder's signature `H(m)^s_i` on a message.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct BlsPartialSignature {
+    /// the index of the share.
+    pub index: usize,
+    /// the partial signature in G2.
+    pub value: G2Projective,
+}
+
+/// Sign a message with a share of a BLS12-381 G1 Feldman sharing.
+pub fn bls_partial_sign(share: (usize, Scalar), message: &[u8]) -> BlsPartialSignature {
+    let (index, value) = share;
+    BlsPartialSignature {
+        index,
+        value: hash_to_g2(message) * value,
+    }
+}
+
+/// Verify a partial signature against the shareholder's key `g1^s_i`,
+/// evaluated from the commitments, with the pairing check
+/// `e(g1, sigma_i) == e(g1^s_i, H(m))`.
+pub fn verify_bls_partial_signature(
+    partial: &BlsPartialSignature,
+    message: &[u8],
+    commitments: &[G1Projective],
+) -> Result<bool, VssError> {
+    let verification_key =
+        VerifiableSecretSharing::evaluate_commitments(commitments, partial.index)?;
+    Ok(verify_bls_signature(
+        &verification_key,
+        message,
+        &partial.value,
+    ))
+}
+
+/// Check every partial signature and combine threshold of the valid ones,
+/// the threshold being the number of commitments, into a signature under
+/// the group key `commitments[0]`.
+///
+/// Partial signatures that do not verify are dropped. Fails with
+/// `VssError::InvalidSignatureShares` listing them only if fewer than
+/// threshold valid ones remain.
+pub fn combine_bls_partial_signatures(
+    partials: &[BlsPartialSignature],
+    message: &[u8],
+    commitments: &[G1Projective],
+) -> Result<G2Projective, VssError> {
+    VerifiableSecretSharing::combine_valid_shares(
+        commitments.len(),
+        partials,
+        |partial| (partial.index, partial.value),
+        |partial| verify_bls_partial_signature(partial, message, commitments),
+        VssError::InvalidSignatureShares,
+    )
+}
+
+/// Verify a BLS signature under a G1 public key, `e(g1, sigma) == e(pk, H(m))`.
+pub fn verify_bls_signature(
+    public_key: &G1Projective,
+    message: &[u8],
+    signature: &G2Projective,
+) -> bool {
+    let public_key = G1Affine::from(public_key);
+    if bool::from(public_key.is_identity()) {
+        return false;
+    }
+    pairing(&G1Affine::generator(), &G2Affine::from(signature))
+        == pairing(&public_key, &G2Affine::from(hash_to_g2(message)))
+}
+
+fn hash_to_g2(message: &[u8]) -> G2Projective {
+    <G2Projective as HashToCurve<ExpandMsgXmd<Sha256>>>::hash_to_curve(message, SIGNATURE_DST)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bls12_381_helper::Bls12381G1;
+    use crate::curve::tests::deal;
+
+    #[test]
+    fn test_threshold_bls_3_of_5_works() {
+        let (secret, shares, commitments) = deal(Bls12381G1, 3, 5);
+        let message = b"threshold bls";
+        let partials: Vec<BlsPartialSignature> = shares
+            .iter()
+            .map(|share| bls_partial_sign(*share, message))
+            .collect();
+        for partial in partials.iter() {
+            assert!(verify_bls_partial_signature(partial, message, &commitments).unwrap());
+        }
+        let signature =
+            combine_bls_partial_signatures(&partials[..3], message, &commitments).unwrap();
+        assert!(verify_bls_signature(&commitments[0], message, &signature));
+        assert!(!verify_bls_signature(
+            &commitments[0],
+            b"another message",
+            &signature
+        ));
+
+        // BLS signatures are unique: any subset yields the dealer's own signature.
+        let other = combine_bls_partial_signatures(&partials[2..], message, &commitments).unwrap();
+        assert_eq!(signature, other);
+        assert_eq!(signature, hash_to_g2(message) * secret);
+    }
+
+    #[test]
+    fn test_threshold_bls_identifies_bad_partial_signature() {
+        let (secret, shares, commitments) = deal(Bls12381G1, 2, 3);
+        let message = b"message";
+        let mut partials: Vec<BlsPartialSignature> = shares
+            .iter()
+            .map(|share| bls_partial_sign(*share, message))
+            .collect();
+        partials[0] = bls_partial_sign(shares[0], b"another message");
+        assert!(!verify_bls_partial_signature(&partials[0], message, &commitments).unwrap());
+        assert_eq!(
+            combine_bls_partial_signatures(&partials, message, &commitments),
+            Ok(hash_to_g2(message) * secret)
+        );
+        partials[2] = bls_partial_sign(shares[1], message);
+        partials[2].index = 3;
+        assert_eq!(
+            combine_bls_partial_signatures(&partials, message, &commitments),
+            Err(VssError::InvalidSignatureShares(vec![1, 3]))
+        );
+    }
+
+    #[test]
+    fn test_threshold_bls_rejects_too_few_partials() {
+        let (_, shares, commitments) = deal(Bls12381G1, 3, 4);
+        let partials = vec![
+            bls_partial_sign(shares[0], b"message"),
+            bls_partial_sign(shares[1], b"message"),
+        ];
+        assert_eq!(
+            combine_bls_partial_signatures(&partials, b"message", &commitments),
+            Err(VssError::TooFewShares {
+                required: 3,
+                provided: 2
+            })
+        );
+    }
+}
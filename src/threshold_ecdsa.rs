@@ -0,0 +1,532 @@
+use crate::curve::Secp256k1;
+use crate::errors::{check_share_indices, VssError};
+use crate::feldman_vss::VerifiableSecretSharing;
+use crate::secp256k1_helper::{get_context, Secp256k1Point, Secp256k1Scalar};
+use rand::{thread_rng, Rng};
+use secp256k1::{Message, Signature};
+
+/// The public part of a signer's first round: Feldman commitments to its
+/// nonce and mask polynomials and to its two zero sharings.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct EcdsaDealing {
+    /// the index of the dealing signer.
+    pub dealer: usize,
+    /// the commitments `g^k_j` to the nonce polynomial, of degree `threshold - 1`.
+    pub nonce_commitments: Vec<Secp256k1Point>,
+    /// the commitments `g^a_j` to the mask polynomial, of degree `threshold - 1`.
+    pub mask_commitments: Vec<Secp256k1Point>,
+    /// the commitments to `q_j` of the zero sharing `x * q(x)` masking the product.
+    pub product_zero_commitments: Vec<Secp256k1Point>,
+    /// the commitments to `q_j` of the zero sharing `x * q(x)` masking the signature.
+    pub signature_zero_commitments: Vec<Secp256k1Point>,
+}
+
+/// The private part of a signer's first round, sent to one recipient.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct EcdsaDealerShares {
+    /// the index of the dealing signer.
+    pub dealer: usize,
+    /// the index of the receiving signer.
+    pub recipient: usize,
+    /// the share of the nonce polynomial.
+    pub nonce: Secp256k1Scalar,
+    /// the share of the mask polynomial.
+    pub mask: Secp256k1Scalar,
+    /// the share of the zero sharing masking the product.
+    pub product_zero: Secp256k1Scalar,
+    /// the share of the zero sharing masking the signature.
+    pub signature_zero: Secp256k1Scalar,
+}
+
+/// A signer's share `k_i * a_i + b_i` of the product `mu = k * a`, broadcast in the second round.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct ProductShare {
+    /// the index of the signer.
+    pub index: usize,
+    /// the masked product share.
+    pub value: Secp256k1Scalar,
+}
+
+/// A signer's share `k^-1_i * (m + r * x_i) + c_i` of `s`, broadcast in the third round.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct EcdsaSignatureShare {
+    /// the index of the signer.
+    pub index: usize,
+    /// the masked signature share.
+    pub value: Secp256k1Scalar,
+}
+
+/// A threshold ECDSA signer holding a share from `VerifiableSecretSharing::split` or a DKG.
+///
+/// This is the honest-majority scheme of Gennaro, Jarecki, Krawczyk and
+/// Rabin: the signers jointly share a random nonce `k` and mask `a`, open
+/// `mu = k * a` by interpolating the degree `2(t - 1)` products, so every
+/// signer holds a share `a_i / mu` of `k^-1`, and finally open
+/// `s = k^-1 * (m + r * x)` the same way. Each product step needs
+/// `2 * threshold - 1` signers, and zero sharings keep the opened values
+/// from leaking the individual shares.
+///
+/// Dealings are checked against their Feldman commitments and the final
+/// signature against the group key, but a signer who sends a wrong product
+/// or signature share is only detected, not identified.
+pub struct EcdsaSigner {
+    /// the index of the share.
+    pub index: usize,
+    share: Secp256k1Scalar,
+    commitments: Vec<Secp256k1Point>,
+    signers: Vec<usize>,
+    dealt: bool,
+    received: Vec<(EcdsaDealing, EcdsaDealerShares)>,
+}
+
+impl EcdsaSigner {
+    /// Create a signer for a session among `signers`, checking the share against the commitments.
+    ///
+    /// Fails with `VssError::InvalidShares` naming the share's index if it
+    /// does not verify against the commitments.
+    pub fn new(
+        share: (usize, Secp256k1Scalar),
+        commitments: &[Secp256k1Point],
+        signers: &[usize],
+    ) -> Result<EcdsaSigner, VssError> {
+        let (index, value) = share;
+        if !VerifiableSecretSharing::try_verify(share, commitments)? {
+            return Err(VssError::InvalidShares(vec![index]));
+        }
+        let required = 2 * commitments.len() - 1;
+        if signers.len() < required {
+            return Err(VssError::TooFewShares {
+                required,
+                provided: signers.len(),
+            });
+        }
+        let indexed: Vec<(usize, ())> = signers.iter().map(|i| (*i, ())).collect();
+        check_share_indices(&indexed)?;
+        if !signers.contains(&index) {
+            return Err(VssError::MissingDealerShare(index));
+        }
+        Ok(EcdsaSigner {
+            index,
+            share: value,
+            commitments: commitments.to_vec(),
+            signers: signers.to_vec(),
+            dealt: false,
+            received: vec![],
+        })
+    }
+
+    /// Round 1: sample the nonce, mask and zero polynomials and share them among the signers.
+    pub fn deal(&mut self) -> (EcdsaDealing, Vec<EcdsaDealerShares>) {
+        self.deal_with_rng(&mut thread_rng())
+    }
+
+    /// Round 1: sample the nonce, mask and zero polynomials from `rng` and share them among the signers.
+    pub fn deal_with_rng<R: Rng + ?Sized>(
+        &mut self,
+        rng: &mut R,
+    ) -> (EcdsaDealing, Vec<EcdsaDealerShares>) {
+        let vss = self.feldman();
+        let nonce = vss.sample_polynomial_with_rng(&Secp256k1Scalar::random(rng), rng);
+        let mask = vss.sample_polynomial_with_rng(&Secp256k1Scalar::random(rng), rng);
+        let zero_degree = 2 * (self.threshold() - 1);
+        let product_zero: Vec<Secp256k1Scalar> = (0..zero_degree)
+            .map(|_| Secp256k1Scalar::random(rng))
+            .collect();
+        let signature_zero: Vec<Secp256k1Scalar> = (0..zero_degree)
+            .map(|_| Secp256k1Scalar::random(rng))
+            .collect();
+
+        let dealing = EcdsaDealing {
+            dealer: self.index,
+            nonce_commitments: VerifiableSecretSharing::generate_commitments(&nonce),
+            mask_commitments: VerifiableSecretSharing::generate_commitments(&mask),
+            product_zero_commitments: VerifiableSecretSharing::generate_commitments(&product_zero),
+            signature_zero_commitments: VerifiableSecretSharing::generate_commitments(
+                &signature_zero,
+            ),
+        };
+        let shares = self
+            .signers
+            .iter()
+            .map(|recipient| EcdsaDealerShares {
+                dealer: self.index,
+                recipient: *recipient,
+                nonce: vss.mod_evaluate_at(&nonce, *recipient),
+                mask: vss.mod_evaluate_at(&mask, *recipient),
+                product_zero: Self::evaluate_zero_sharing(&vss, &product_zero, *recipient),
+                signature_zero: Self::evaluate_zero_sharing(&vss, &signature_zero, *recipient),
+            })
+            .collect();
+        self.dealt = true;
+        (dealing, shares)
+    }
+
+    /// Check a dealing and the shares addressed to this signer, keeping them if valid.
+    ///
+    /// Fails with `VssError::InvalidShares` naming the dealer if the
+    /// shares do not match the commitments, and with
+    /// `VssError::MissingDealerShare` if the dealer is not among the signers.
+    pub fn receive(
+        &mut self,
+        dealing: &EcdsaDealing,
+        shares: &EcdsaDealerShares,
+    ) -> Result<(), VssError> {
+        if !self.signers.contains(&dealing.dealer) {
+            return Err(VssError::MissingDealerShare(dealing.dealer));
+        }
+        if !self.check_dealing(dealing, shares)? {
+            return Err(VssError::InvalidShares(vec![dealing.dealer]));
+        }
+        self.received.retain(|(d, _)| d.dealer != dealing.dealer);
+        self.received.push((dealing.clone(), *shares));
+        Ok(())
+    }
+
+    /// Round 2: the masked share of `mu = k * a`, once every signer's dealing was received.
+    pub fn product_share(&self) -> Result<ProductShare, VssError> {
+        let shares = self.summed_shares()?;
+        Ok(ProductShare {
+            index: self.index,
+            value: shares.nonce * shares.mask + shares.product_zero,
+        })
+    }
+
+    /// Round 3: open `mu` from every signer's product share and compute this
+    /// signer's masked share of `s` for the 32 bytes message digest.
+    pub fn sign(
+        &self,
+        message: &Message,
+        products: &[ProductShare],
+    ) -> Result<EcdsaSignatureShare, VssError> {
+        let shares = self.summed_shares()?;
+        let values: Vec<(usize, Secp256k1Scalar)> =
+            products.iter().map(|p| (p.index, p.value)).collect();
+        let mu = self.interpolate(&values)?;
+        if mu == Secp256k1Scalar::zero() {
+            return Err(VssError::InvalidSignatureShares(self.signers.clone()));
+        }
+        let inverse_nonce = shares.mask * mu.inverse();
+        let r = Self::nonce_x(self.nonce()?);
+        Ok(EcdsaSignatureShare {
+            index: self.index,
+            value: inverse_nonce * (Self::message_scalar(message) + r * self.share)
+                + shares.signature_zero,
+        })
+    }
+
+    /// Open `s` from every signer's signature share and check the low-s
+    /// normalized signature against the group key.
+    ///
+    /// An identity group key has no signatures, so combining under it fails
+    /// like any signature that does not verify.
+    pub fn combine(
+        &self,
+        message: &Message,
+        shares: &[EcdsaSignatureShare],
+    ) -> Result<Signature, VssError> {
+        let values: Vec<(usize, Secp256k1Scalar)> =
+            shares.iter().map(|s| (s.index, s.value)).collect();
+        let s = self.interpolate(&values)?;
+        let r = Self::nonce_x(self.nonce()?);
+        let mut compact = [0u8; 64];
+        compact[..32].copy_from_slice(&r.serialize());
+        compact[32..].copy_from_slice(&s.serialize());
+        let mut signature = Signature::from_compact(&compact)
+            .map_err(|_| VssError::InvalidSignatureShares(self.signers.clone()))?;
+        signature.normalize_s();
+        if self.commitments[0].is_identity() {
+            return Err(VssError::InvalidSignatureShares(self.signers.clone()));
+        }
+        get_context()
+            .verify(message, &signature, self.commitments[0].as_public_key())
+            .map_err(|_| VssError::InvalidSignatureShares(self.signers.clone()))?;
+        Ok(signature)
+    }
+
+    fn threshold(&self) -> usize {
+        self.commitments.len()
+    }
+
+    fn feldman(&self) -> VerifiableSecretSharing {
+        VerifiableSecretSharing {
+            threshold: self.threshold(),
+            share_amount: self.signers.len(),
+            curve: Secp256k1,
+        }
+    }
+
+    /// `x * q(x)`, a share of a polynomial of degree `2(t - 1)` with a zero constant term.
+    fn evaluate_zero_sharing(
+        vss: &VerifiableSecretSharing,
+        polynomial: &[Secp256k1Scalar],
+        x: usize,
+    ) -> Secp256k1Scalar {
+        let scalar_x = Secp256k1Scalar::from_u64(x as u64);
+        vss.mod_evaluate_at(polynomial, x) * scalar_x
+    }
+
+    fn check_dealing(
+        &self,
+        dealing: &EcdsaDealing,
+        shares: &EcdsaDealerShares,
+    ) -> Result<bool, VssError> {
+        let zero_degree = 2 * (self.threshold() - 1);
+        if shares.dealer != dealing.dealer
+            || shares.recipient != self.index
+            || dealing.nonce_commitments.len() != self.threshold()
+            || dealing.mask_commitments.len() != self.threshold()
+            || dealing.product_zero_commitments.len() != zero_degree
+            || dealing.signature_zero_commitments.len() != zero_degree
+        {
+            return Ok(false);
+        }
+        let nonce_valid = VerifiableSecretSharing::try_verify(
+            (self.index, shares.nonce),
+            &dealing.nonce_commitments,
+        )?;
+        let mask_valid = VerifiableSecretSharing::try_verify(
+            (self.index, shares.mask),
+            &dealing.mask_commitments,
+        )?;
+        Ok(nonce_valid
+            && mask_valid
+            && self.check_zero_share(shares.product_zero, &dealing.product_zero_commitments)?
+            && self.check_zero_share(shares.signature_zero, &dealing.signature_zero_commitments)?)
+    }
+
+    fn check_zero_share(
+        &self,
+        share: Secp256k1Scalar,
+        commitments: &[Secp256k1Point],
+    ) -> Result<bool, VssError> {
+        if commitments.is_empty() {
+            return Ok(share == Secp256k1Scalar::zero());
+        }
+        let scalar_index = Secp256k1Scalar::from_u64(self.index as u64);
+        let expected =
+            VerifiableSecretSharing::evaluate_commitments(commitments, self.index)? * scalar_index;
+        Ok(Secp256k1Point::generator() * share == expected)
+    }
+
+    fn summed_shares(&self) -> Result<EcdsaDealerShares, VssError> {
+        if !self.dealt {
+            return Err(VssError::UnexpectedRound);
+        }
+        let mut sum = EcdsaDealerShares {
+            dealer: self.index,
+            recipient: self.index,
+            nonce: Secp256k1Scalar::zero(),
+            mask: Secp256k1Scalar::zero(),
+            product_zero: Secp256k1Scalar::zero(),
+            signature_zero: Secp256k1Scalar::zero(),
+        };
+        for dealer in self.signers.iter() {
+            let (_, shares) = self
+                .received
+                .iter()
+                .find(|(dealing, _)| dealing.dealer == *dealer)
+                .ok_or(VssError::MissingDealerShare(*dealer))?;
+            sum.nonce = sum.nonce + shares.nonce;
+            sum.mask = sum.mask + shares.mask;
+            sum.product_zero = sum.product_zero + shares.product_zero;
+            sum.signature_zero = sum.signature_zero + shares.signature_zero;
+        }
+        Ok(sum)
+    }
+
+    /// The joint nonce point `R = g^k`, the sum of the dealers' constant term
+    /// commitments, once every signer's dealing was received.
+    fn nonce(&self) -> Result<Secp256k1Point, VssError> {
+        if self.received.len() < self.signers.len() {
+            return Err(VssError::TooFewShares {
+                required: self.signers.len(),
+                provided: self.received.len(),
+            });
+        }
+        Ok(self
+            .received
+            .iter()
+            .fold(Secp256k1Point::identity(), |sum, (dealing, _)| {
+                sum + dealing.nonce_commitments[0]
+            }))
+    }
+
+    /// Interpolate the value at zero of the degree `2(t - 1)` polynomial
+    /// through one value per signer.
+    fn interpolate(
+        &self,
+        values: &[(usize, Secp256k1Scalar)],
+    ) -> Result<Secp256k1Scalar, VssError> {
+        check_share_indices(values)?;
+        for signer in self.signers.iter() {
+            if !values.iter().any(|(index, _)| index == signer) {
+                return Err(VssError::MissingDealerShare(*signer));
+            }
+        }
+        let xs: Vec<usize> = values.iter().map(|(index, _)| *index).collect();
+        Ok(VerifiableSecretSharing::<Secp256k1>::lagrange_coefficients(
+            Secp256k1Scalar::zero(),
+            &xs,
+        )
+        .iter()
+        .zip(values.iter())
+        .fold(Secp256k1Scalar::zero(), |sum, (coefficient, (_, value))| {
+            sum + *coefficient * *value
+        }))
+    }
+
+    fn nonce_x(nonce: Secp256k1Point) -> Secp256k1Scalar {
+        Secp256k1Scalar::from_bytes_mod_order(&nonce.serialize()[1..])
+    }
+
+    fn message_scalar(message: &Message) -> Secp256k1Scalar {
+        Secp256k1Scalar::from_bytes_mod_order(&message[..])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curve::tests::deal;
+
+    fn new_signers(
+        shares: &[(usize, Secp256k1Scalar)],
+        commitments: &[Secp256k1Point],
+        signers: &[usize],
+    ) -> Vec<EcdsaSigner> {
+        shares
+            .iter()
+            .filter(|(index, _)| signers.contains(index))
+            .map(|share| EcdsaSigner::new(*share, commitments, signers).unwrap())
+            .collect()
+    }
+
+    fn exchange_dealings(signers: &mut [EcdsaSigner]) {
+        let dealings: Vec<(EcdsaDealing, Vec<EcdsaDealerShares>)> =
+            signers.iter_mut().map(|signer| signer.deal()).collect();
+        for signer in signers.iter_mut() {
+            for (dealing, shares) in dealings.iter() {
+                let own = shares.iter().find(|s| s.recipient == signer.index).unwrap();
+                signer.receive(dealing, own).unwrap();
+            }
+        }
+    }
+
+    fn sign(signers: &mut [EcdsaSigner], message: &Message) -> Vec<EcdsaSignatureShare> {
+        exchange_dealings(signers);
+        let products: Vec<ProductShare> = signers
+            .iter()
+            .map(|signer| signer.product_share().unwrap())
+            .collect();
+        signers
+            .iter()
+            .map(|signer| signer.sign(message, &products).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_threshold_ecdsa_verifies_with_secp256k1() {
+        for _ in 0..4 {
+            let (_, shares, commitments) = deal(Secp256k1, 3, 6);
+            let mut signers = new_signers(&shares, &commitments, &[1, 2, 4, 5, 6]);
+            let message = Message::from_slice(&[0xab; 32]).expect("32 bytes");
+            let shares = sign(&mut signers, &message);
+            let signature = signers[2].combine(&message, &shares).unwrap();
+
+            assert!(get_context()
+                .verify(&message, &signature, commitments[0].as_public_key())
+                .is_ok());
+        }
+    }
+
+    #[test]
+    fn test_threshold_ecdsa_combine_rejects_identity_group_key() {
+        let vss = VerifiableSecretSharing {
+            threshold: 2,
+            share_amount: 3,
+            curve: Secp256k1,
+        };
+        let (shares, commitments) = vss.split(&Secp256k1Scalar::zero());
+        let mut signers: Vec<EcdsaSigner> = shares
+            .into_iter()
+            .map(|share| EcdsaSigner::new(share, &commitments, &[1, 2, 3]).unwrap())
+            .collect();
+        let message = Message::from_slice(&[0x17; 32]).expect("32 bytes");
+        let shares = sign(&mut signers, &message);
+        assert_eq!(
+            signers[0].combine(&message, &shares),
+            Err(VssError::InvalidSignatureShares(vec![1, 2, 3]))
+        );
+    }
+
+    #[test]
+    fn test_threshold_ecdsa_rejects_too_few_signers() {
+        let (_, shares, commitments) = deal(Secp256k1, 3, 5);
+        assert_eq!(
+            EcdsaSigner::new(shares[0], &commitments, &[1, 2, 3, 4]).err(),
+            Some(VssError::TooFewShares {
+                required: 5,
+                provided: 4
+            })
+        );
+    }
+
+    #[test]
+    fn test_threshold_ecdsa_rejects_invalid_key_share_and_early_combine() {
+        let (_, shares, commitments) = deal(Secp256k1, 2, 3);
+        let forged = (shares[0].0, shares[0].1 + Secp256k1Scalar::one());
+        assert_eq!(
+            EcdsaSigner::new(forged, &commitments, &[1, 2, 3]).err(),
+            Some(VssError::InvalidShares(vec![1]))
+        );
+
+        let signer = EcdsaSigner::new(shares[0], &commitments, &[1, 2, 3]).unwrap();
+        let message = Message::from_slice(&[0x42; 32]).expect("32 bytes");
+        let early: Vec<EcdsaSignatureShare> = (1..=3)
+            .map(|index| EcdsaSignatureShare {
+                index,
+                value: Secp256k1Scalar::new_random(),
+            })
+            .collect();
+        assert_eq!(
+            signer.combine(&message, &early),
+            Err(VssError::TooFewShares {
+                required: 3,
+                provided: 0
+            })
+        );
+    }
+
+    #[test]
+    fn test_threshold_ecdsa_rejects_bad_dealing() {
+        let (_, shares, commitments) = deal(Secp256k1, 2, 3);
+        let mut signers = new_signers(&shares, &commitments, &[1, 2, 3]);
+        let (dealing, mut shares) = signers[0].deal();
+        shares[1].mask = shares[1].mask + Secp256k1Scalar::one();
+        assert_eq!(
+            signers[1].receive(&dealing, &shares[1]),
+            Err(VssError::InvalidShares(vec![1]))
+        );
+        assert_eq!(signers[1].product_share(), Err(VssError::UnexpectedRound));
+        let (_, shares, commitments) = deal(Secp256k1, 2, 4);
+        let mut outsiders = new_signers(&shares, &commitments, &[2, 3, 4]);
+        let (dealing, shares) = outsiders[2].deal();
+        assert_eq!(
+            signers[1].receive(&dealing, &shares[0]),
+            Err(VssError::MissingDealerShare(4))
+        );
+    }
+
+    #[test]
+    fn test_threshold_ecdsa_detects_bad_signature_share() {
+        let (_, shares, commitments) = deal(Secp256k1, 2, 3);
+        let mut signers = new_signers(&shares, &commitments, &[1, 2, 3]);
+        let message = Message::from_slice(&[0x42; 32]).expect("32 bytes");
+        let mut shares = sign(&mut signers, &message);
+        shares[0].value = shares[0].value + Secp256k1Scalar::one();
+        assert_eq!(
+            signers[1].combine(&message, &shares),
+            Err(VssError::InvalidSignatureShares(vec![1, 2, 3]))
+        );
+    }
+}
@@ -0,0 +1,176 @@
+use crate::dleq::{DleqProof, DleqStatement};
+use crate::errors::VssError;
+use crate::feldman_vss::VerifiableSecretSharing;
+use crate::secp256k1_helper::{Secp256k1Point, Secp256k1Scalar};
+use crate::transcript::Transcript;
+use rand::{thread_rng, Rng};
+
+/// An ElGamal ciphertext `(c1, c2) = (g^r, M * y^r)` of a point `M` under the group key `y`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct ElGamalCiphertext {
+    /// the ephemeral key `c1 = g^r`.
+    pub c1: Secp256k1Point,
+    /// the masked message `c2 = M * y^r`.
+    pub c2: Secp256k1Point,
+}
+
+/// A shareholder's partial decryption `D_i = c1^s_i`, with a proof of correctness.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct PartialDecryption {
+    /// the index of the share.
+    pub index: usize,
+    /// the partial decryption `D_i`.
+    pub value: Secp256k1Point,
+    /// the proof that `log_g(g^s_i) == log_c1(D_i)`.
+    pub proof: DleqProof,
+}
+
+/// Encrypt a point to the group whose key `y = g^s` is the first Feldman commitment.
+pub fn encrypt_to_group(
+    public_key: &Secp256k1Point,
+    message: &Secp256k1Point,
+) -> ElGamalCiphertext {
+    encrypt_to_group_with_rng(public_key, message, &mut thread_rng())
+}
+
+/// Encrypt a point to the group key, sampling the ephemeral key from `rng`.
+pub fn encrypt_to_group_with_rng<R: Rng + ?Sized>(
+    public_key: &Secp256k1Point,
+    message: &Secp256k1Point,
+    rng: &mut R,
+) -> ElGamalCiphertext {
+    let ephemeral = Secp256k1Scalar::random(rng);
+    ElGamalCiphertext {
+        c1: Secp256k1Point::generator() * ephemeral,
+        c2: *message + *public_key * ephemeral,
+    }
+}
+
+/// Compute a share's partial decryption of the ciphertext and prove it was done correctly.
+pub fn partial_decrypt(
+    ciphertext: &ElGamalCiphertext,
+    share: (usize, Secp256k1Scalar),
+) -> PartialDecryption {
+    let (index, value) = share;
+    let partial = ciphertext.c1 * value;
+    let statement = DleqStatement {
+        g1: Secp256k1Point::generator(),
+        h1: Secp256k1Point::generator() * value,
+        g2: ciphertext.c1,
+        h2: partial,
+    };
+    let mut transcript = decryption_transcript(ciphertext, index);
+    PartialDecryption {
+        index,
+        value: partial,
+        proof: DleqProof::prove(&mut transcript, &[statement], &[value]),
+    }
+}
+
+/// Verify a partial decryption against the shareholder's key `g^s_i`, evaluated from the commitments.
+pub fn verify_partial_decryption(
+    ciphertext: &ElGamalCiphertext,
+    partial: &PartialDecryption,
+    commitments: &[Secp256k1Point],
+) -> Result<bool, VssError> {
+    let statement = DleqStatement {
+        g1: Secp256k1Point::generator(),
+        h1: VerifiableSecretSharing::evaluate_commitments(commitments, partial.index)?,
+        g2: ciphertext.c1,
+        h2: partial.value,
+    };
+    let mut transcript = decryption_transcript(ciphertext, partial.index);
+    Ok(partial.proof.verify(&mut transcript, &[statement]))
+}
+
+/// Check every partial decryption and combine threshold of the valid ones,
+/// the threshold being the number of commitments, to recover the message.
+///
+/// Partial decryptions that do not verify are dropped. Fails with
+/// `VssError::InvalidDecryptionShares` listing them only if fewer than
+/// threshold valid ones remain.
+pub fn combine_partial_decryptions(
+    ciphertext: &ElGamalCiphertext,
+    partials: &[PartialDecryption],
+    commitments: &[Secp256k1Point],
+) -> Result<Secp256k1Point, VssError> {
+    let mask = VerifiableSecretSharing::combine_valid_shares(
+        commitments.len(),
+        partials,
+        |partial| (partial.index, partial.value),
+        |partial| verify_partial_decryption(ciphertext, partial, commitments),
+        VssError::InvalidDecryptionShares,
+    )?;
+    Ok(ciphertext.c2 + -mask)
+}
+
+fn decryption_transcript(ciphertext: &ElGamalCiphertext, index: usize) -> Transcript {
+    let mut transcript = Transcript::new(b"verifiable_secret_sharing/threshold_elgamal/decryption");
+    transcript.append_point(b"c1", &ciphertext.c1);
+    transcript.append_point(b"c2", &ciphertext.c2);
+    transcript.append_u64(b"index", index as u64);
+    transcript
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curve::tests::deal;
+    use crate::curve::Secp256k1;
+
+    #[test]
+    fn test_threshold_elgamal_3_of_5_works() {
+        let (_, shares, commitments) = deal(Secp256k1, 3, 5);
+        let message = Secp256k1Point::hash_to_curve(b"attack at dawn");
+        let ciphertext = encrypt_to_group(&commitments[0], &message);
+        let partials: Vec<PartialDecryption> = shares[1..4]
+            .iter()
+            .map(|share| partial_decrypt(&ciphertext, *share))
+            .collect();
+        for partial in partials.iter() {
+            assert!(verify_partial_decryption(&ciphertext, partial, &commitments).unwrap());
+        }
+        assert_eq!(
+            combine_partial_decryptions(&ciphertext, &partials, &commitments),
+            Ok(message)
+        );
+    }
+
+    #[test]
+    fn test_threshold_elgamal_identifies_bad_partial_decryption() {
+        let (_, shares, commitments) = deal(Secp256k1, 2, 3);
+        let message = Secp256k1Point::hash_to_curve(b"message");
+        let ciphertext = encrypt_to_group(&commitments[0], &message);
+        let mut partials: Vec<PartialDecryption> = shares
+            .iter()
+            .map(|share| partial_decrypt(&ciphertext, *share))
+            .collect();
+        partials[0].value = partials[0].value + Secp256k1Point::generator();
+        assert_eq!(
+            combine_partial_decryptions(&ciphertext, &partials, &commitments),
+            Ok(message)
+        );
+        partials[2].value = partials[2].value + Secp256k1Point::generator();
+        assert_eq!(
+            combine_partial_decryptions(&ciphertext, &partials, &commitments),
+            Err(VssError::InvalidDecryptionShares(vec![1, 3]))
+        );
+    }
+
+    #[test]
+    fn test_threshold_elgamal_rejects_too_few_partials() {
+        let (_, shares, commitments) = deal(Secp256k1, 3, 4);
+        let ciphertext = encrypt_to_group(&commitments[0], &Secp256k1Point::generator());
+        let partials = vec![
+            partial_decrypt(&ciphertext, shares[0]),
+            partial_decrypt(&ciphertext, shares[1]),
+        ];
+        assert_eq!(
+            combine_partial_decryptions(&ciphertext, &partials, &commitments),
+            Err(VssError::TooFewShares {
+                required: 3,
+                provided: 2
+            })
+        );
+    }
+}